@@ -1,8 +1,20 @@
-use crate::lexical_parsing::symbols::{Keyword, Operator, Symbol, TypeSymbol};
+use crate::address::{Address, DIRECT_ADDRESS_BYTES};
+use crate::bx;
+use crate::lexical_parsing::symbols::{Keyword, Literal, Operator, Symbol, TypeSymbol};
 use crate::memory::MemoryManager;
 use crate::processing::blocks::StackSizes;
+use crate::processing::instructions::binary_not_7::BinaryNotInstruction;
+use crate::processing::instructions::copy_3::CopyInstruction;
+use crate::processing::instructions::jump_if_not_9::JumpIfNotInstruction;
 use crate::processing::reference_manager::ReferenceStack;
-use crate::processing::symbols::{Keyword, Operator, Symbol, TypeSymbol};
+use crate::processing::symbols::{Keyword, Literal, Operator, Symbol, TypeSymbol};
+use crate::processing::types::boolean::{BoolType, BOOLEAN_SIZE, BOOL_FALSE};
+use crate::processing::types::enumeration::EnumType;
+use crate::processing::types::float::FloatType;
+use crate::processing::types::integer::IntegerType;
+use crate::processing::types::optional::OptionalType;
+use crate::processing::types::tuple::TupleType;
+use crate::processing::types::typed_pointer::TypedPointerType;
 use crate::processing::types::{Type, TypeFactory};
 use crate::util::ref_or_box::RefOrBox;
 
@@ -142,6 +154,18 @@ fn evaluate_arithmetic_section<'a>(
         );
     }
 
+    // ? Enum construction with a payload e.g. shape.circle(5)
+    if let [Symbol::Name(name), Symbol::BracketedSection(payload)] = section {
+        return handle_enum_construction(
+            name,
+            Some(payload),
+            return_options,
+            program_memory,
+            reference_stack,
+            stack_sizes,
+        );
+    }
+
     match &section[0] {
         // ? Prefix operator e.g. ! A
         Symbol::Operator(operator) => {
@@ -149,6 +173,21 @@ fn evaluate_arithmetic_section<'a>(
                 return Err("Operator must be followed by a Literal or Name".to_string());
             }
 
+            // ? Pointer and optional operators e.g. ref a, deref p, unwrap o
+            if matches!(
+                operator,
+                Operator::AddressOf | Operator::Dereference | Operator::Unwrap
+            ) {
+                return handle_pointer_or_optional_operation(
+                    operator,
+                    &section[1],
+                    return_options,
+                    program_memory,
+                    reference_stack,
+                    stack_sizes,
+                );
+            }
+
             let return_option = if let Some(preference) = return_options.get_prefered_type() {
                 ReturnOptions::PreferType(preference)
             } else {
@@ -215,9 +254,33 @@ fn evaluate_arithmetic_section<'a>(
                     )?
                     .unwrap();
 
+                    // ? Boolean & and | only evaluate the rhs if needed
+                    if matches!(operator, Operator::And | Operator::Or)
+                        && lhs.as_ref().get_type_symbol() == TypeSymbol::Boolean
+                    {
+                        return handle_short_circuit(
+                            operator,
+                            lhs,
+                            &section[2],
+                            return_options,
+                            program_memory,
+                            reference_stack,
+                            stack_sizes,
+                        );
+                    }
+
+                    // ? Literals take the type of the value they are operated on with where
+                    // ? possible e.g. `u8 a; a < 5` compares two u8s
+                    let rhs_return_option = match &section[2] {
+                        Symbol::Literal(_) => {
+                            ReturnOptions::PreferType(lhs.as_ref().get_type_symbol())
+                        }
+                        _ => return_option,
+                    };
+
                     let rhs = handle_single_symbol(
                         &section[2],
-                        &return_option,
+                        &rhs_return_option,
                         program_memory,
                         reference_stack,
                         stack_sizes,
@@ -240,11 +303,17 @@ fn evaluate_arithmetic_section<'a>(
 }
 
 fn incorrect_type_error(expected: &[TypeSymbol], received: &[TypeSymbol]) -> String {
+    // ? Enums are told apart by name
+    fn type_name(t: &TypeSymbol) -> String {
+        t.get_enum_name()
+            .map_or_else(|| t.to_string(), |name| name.to_string())
+    }
+
     let mut expected_text = "[any]".to_string();
     if !expected.is_empty() {
         expected_text = "[".to_string();
         for e in expected {
-            expected_text += (e.to_string() + ", ").as_str();
+            expected_text += (type_name(e) + ", ").as_str();
         }
         expected_text = expected_text[..expected_text.len() - 2].to_string();
     }
@@ -253,7 +322,7 @@ fn incorrect_type_error(expected: &[TypeSymbol], received: &[TypeSymbol]) -> Str
     if !received.is_empty() {
         received_text = "[".to_string();
         for r in received {
-            received_text += (r.to_string() + ", ").as_str();
+            received_text += (type_name(r) + ", ").as_str();
         }
         received_text = received_text[..received_text.len() - 2].to_string();
     }
@@ -273,6 +342,23 @@ fn handle_single_symbol<'a>(
 ) -> Result<Option<RefOrBox<'a, dyn Type + 'a>>, String> {
     match symbol {
         Symbol::Name(name) => {
+            // ? Enum construction without a payload e.g. shape.square
+            if name.len() > 1
+                && reference_stack
+                    .get_reference(&name[..name.len() - 1])
+                    .and_then(|reference| reference.get_enum_ref())
+                    .is_ok()
+            {
+                return handle_enum_construction(
+                    name,
+                    None,
+                    return_options,
+                    program_memory,
+                    reference_stack,
+                    stack_sizes,
+                );
+            }
+
             let variable = reference_stack.get_reference(name)?.get_variable_ref()?;
             match return_options {
                 ReturnOptions::IntoType(output) => {
@@ -306,30 +392,16 @@ fn handle_single_symbol<'a>(
                     output.runtime_copy_from_literal(literal, program_memory)?;
                     Ok(None)
                 }
+                // ? Literals are used as immediates so operations on them can be folded
                 ReturnOptions::AnyType => Ok(Some(RefOrBox::from_box(
-                    TypeFactory::get_default_instantiated_type_for_literal(
-                        literal,
-                        stack_sizes,
-                        program_memory,
-                        None,
-                    )?,
+                    TypeFactory::get_constant_type_for_literal(literal, None)?,
                 ))),
                 ReturnOptions::PreferType(preferred) => Ok(Some(RefOrBox::from_box(
-                    TypeFactory::get_default_instantiated_type_for_literal(
-                        literal,
-                        stack_sizes,
-                        program_memory,
-                        Some(preferred),
-                    )?,
+                    TypeFactory::get_constant_type_for_literal(literal, Some(preferred))?,
                 ))),
                 ReturnOptions::OneOfTypes(types) => {
-                    // TODO: Potentially request types from literals i.e. not default
-                    let default_type = TypeFactory::get_default_instantiated_type_for_literal(
-                        literal,
-                        stack_sizes,
-                        program_memory,
-                        None,
-                    )?;
+                    let default_type =
+                        TypeFactory::get_constant_type_for_literal(literal, types.first())?;
                     let default_type_type = default_type.get_type_symbol();
                     if !types.is_empty() && !types.iter().any(|t| *t == default_type_type) {
                         Err(incorrect_type_error(types, &[default_type_type]))
@@ -349,10 +421,451 @@ fn handle_single_symbol<'a>(
                 stack_sizes,
             )
         }
+        // ? Tuple e.g. (a, 5)
+        Symbol::List(items) => handle_tuple_construction(
+            items,
+            return_options,
+            program_memory,
+            reference_stack,
+            stack_sizes,
+        ),
+        // ? Tuple element e.g. t[0]
+        Symbol::Indexer(applied_to, index) => handle_tuple_index(
+            applied_to,
+            index,
+            return_options,
+            program_memory,
+            reference_stack,
+            stack_sizes,
+        ),
         _ => Err("Expected an expression".to_string()),
     }
 }
 
+/// Returns an already evaluated value in the form requested by `return_options`
+fn return_value<'a>(
+    value: RefOrBox<'a, dyn Type + 'a>,
+    return_options: &ReturnOptions,
+    program_memory: &mut MemoryManager,
+) -> Result<Option<RefOrBox<'a, dyn Type + 'a>>, String> {
+    match return_options {
+        ReturnOptions::IntoType(output) => {
+            output.runtime_copy_from(value.as_ref(), program_memory)?;
+            Ok(None)
+        }
+        ReturnOptions::AnyType | ReturnOptions::PreferType(_) => Ok(Some(value)),
+        ReturnOptions::OneOfTypes(types) => {
+            let value_type = value.as_ref().get_type_symbol();
+            if !types.is_empty() && !types.iter().any(|t| *t == value_type) {
+                Err(incorrect_type_error(types, &[value_type]))
+            } else {
+                Ok(Some(value))
+            }
+        }
+    }
+}
+
+fn handle_tuple_construction<'a>(
+    items: &[Vec<Symbol>],
+    return_options: &ReturnOptions,
+    program_memory: &mut MemoryManager,
+    reference_stack: &'a ReferenceStack,
+    stack_sizes: &mut StackSizes,
+) -> Result<Option<RefOrBox<'a, dyn Type + 'a>>, String> {
+    if items.iter().any(|item| item.is_empty()) {
+        return Err("Tuple elements cannot be empty".to_string());
+    }
+
+    // ? Build directly into the destination if it is a tuple of the same size
+    if let ReturnOptions::IntoType(output) = return_options {
+        if let Some(elements) = output
+            .get_type_symbol()
+            .get_tuple_elements()
+            .filter(|elements| elements.len() == items.len())
+        {
+            // ? Build into a temporary if an element reads the destination e.g. t = (t[1], t[0])
+            if items
+                .iter()
+                .any(|item| may_read_destination(item, *output, reference_stack))
+            {
+                let tuple = handle_tuple_construction(
+                    items,
+                    &ReturnOptions::PreferType(output.get_type_symbol()),
+                    program_memory,
+                    reference_stack,
+                    stack_sizes,
+                )?
+                .unwrap();
+                return return_value(tuple, return_options, program_memory);
+            }
+
+            let mut tuple = TupleType::new(elements)?;
+            tuple.set_address(output.get_address().clone());
+
+            for (index, item) in items.iter().enumerate() {
+                let element = tuple.get_element_view(index)?;
+                evaluate_arithmetic_into_type(
+                    item,
+                    element.as_ref(),
+                    program_memory,
+                    reference_stack,
+                    stack_sizes,
+                )?;
+            }
+            return Ok(None);
+        }
+    }
+
+    let preferred_elements = return_options
+        .get_prefered_type()
+        .and_then(|t| t.get_tuple_elements())
+        .filter(|elements| elements.len() == items.len());
+
+    let mut values = Vec::with_capacity(items.len());
+    for (index, item) in items.iter().enumerate() {
+        let item_options = match preferred_elements {
+            Some(elements) => ReturnOptions::PreferType(elements[index]),
+            None => ReturnOptions::AnyType,
+        };
+
+        values.push(
+            evaluate_arithmetic_section(
+                item,
+                &item_options,
+                program_memory,
+                reference_stack,
+                stack_sizes,
+            )?
+            .unwrap(),
+        );
+    }
+
+    let element_types: Vec<TypeSymbol> = values
+        .iter()
+        .map(|value| value.as_ref().get_type_symbol())
+        .collect();
+    let tuple_symbol = TypeSymbol::tuple_of(&element_types);
+
+    let mut tuple = TupleType::new(tuple_symbol.get_tuple_elements().unwrap())?;
+    tuple.allocate_variable(stack_sizes, program_memory)?;
+    for (index, value) in values.iter().enumerate() {
+        tuple
+            .get_element_view(index)?
+            .runtime_copy_from(value.as_ref(), program_memory)?;
+    }
+
+    let tuple: Box<dyn Type> = bx!(tuple);
+    return_value(RefOrBox::from_box(tuple), return_options, program_memory)
+}
+
+/// Returns whether evaluating `symbols` could read memory that overlaps `destination`
+fn may_read_destination(
+    symbols: &[Symbol],
+    destination: &dyn Type,
+    reference_stack: &ReferenceStack,
+) -> bool {
+    symbols.iter().any(|symbol| match symbol {
+        Symbol::Name(name) => match reference_stack
+            .get_reference(name)
+            .and_then(|reference| reference.get_variable_ref())
+        {
+            Ok(variable) => match (variable.get_address(), destination.get_address()) {
+                (Address::Immediate(_), _) | (_, Address::Immediate(_)) => false,
+                (Address::StackDirect(start), Address::StackDirect(destination_start)) => {
+                    *start < *destination_start + destination.get_length()
+                        && *destination_start < *start + variable.get_length()
+                }
+                // ? Other addresses can't be compared at compile time
+                _ => true,
+            },
+            Err(_) => false,
+        },
+        // ? A dereferenced pointer could point anywhere
+        Symbol::Operator(Operator::Dereference) => true,
+        Symbol::BracketedSection(section) => {
+            may_read_destination(section, destination, reference_stack)
+        }
+        Symbol::Indexer(value, index) => {
+            may_read_destination(
+                std::slice::from_ref(value.as_ref()),
+                destination,
+                reference_stack,
+            ) || may_read_destination(index, destination, reference_stack)
+        }
+        Symbol::List(items) => items
+            .iter()
+            .any(|item| may_read_destination(item, destination, reference_stack)),
+        Symbol::MethodCall(value, _, arguments) => {
+            may_read_destination(
+                std::slice::from_ref(value.as_ref()),
+                destination,
+                reference_stack,
+            ) || arguments
+                .iter()
+                .any(|argument| may_read_destination(argument, destination, reference_stack))
+        }
+        _ => false,
+    })
+}
+
+fn handle_tuple_index<'a>(
+    applied_to: &Symbol,
+    index: &[Symbol],
+    return_options: &ReturnOptions,
+    program_memory: &mut MemoryManager,
+    reference_stack: &'a ReferenceStack,
+    stack_sizes: &mut StackSizes,
+) -> Result<Option<RefOrBox<'a, dyn Type + 'a>>, String> {
+    let index = match index {
+        [Symbol::Literal(Literal::Int(index) | Literal::TypedInt(index, _))] => {
+            usize::try_from(*index).ok()
+        }
+        _ => None,
+    };
+    let Some(index) = index else {
+        return Err("Tuples can only be indexed with a constant e.g. t[0]".to_string());
+    };
+
+    let tuple = handle_single_symbol(
+        applied_to,
+        &ReturnOptions::AnyType,
+        program_memory,
+        reference_stack,
+        stack_sizes,
+    )?
+    .unwrap();
+    let tuple = tuple.as_ref();
+
+    let Some(elements) = tuple.get_type_symbol().get_tuple_elements() else {
+        return Err(format!(
+            "Only tuples can be indexed, received {}",
+            tuple.get_type_symbol()
+        ));
+    };
+
+    let mut view = TupleType::new(elements)?;
+    view.set_address(tuple.get_address().clone());
+    let element = view.get_element_view(index)?;
+
+    return_value(RefOrBox::from_box(element), return_options, program_memory)
+}
+
+fn handle_pointer_or_optional_operation<'a>(
+    operator: &Operator,
+    operand: &Symbol,
+    return_options: &ReturnOptions,
+    program_memory: &mut MemoryManager,
+    reference_stack: &'a ReferenceStack,
+    stack_sizes: &mut StackSizes,
+) -> Result<Option<RefOrBox<'a, dyn Type + 'a>>, String> {
+    match operator {
+        Operator::AddressOf => {
+            let variable = match operand {
+                Symbol::Name(name) => reference_stack.get_reference(name)?.get_variable_ref()?,
+                _ => return Err(format!("{} can only be applied to a variable", operator)),
+            };
+
+            // ? Only direct addresses can be followed by an indirect address
+            if variable.get_address().to_indirect().is_none() {
+                return Err(format!(
+                    "Can't take the address of a value that is not stored directly ({})",
+                    variable.get_type_symbol()
+                ));
+            }
+
+            let pointer_type = TypeSymbol::pointer_to(variable.get_type_symbol());
+            let pointer_value = Address::Immediate(variable.get_address().get_bytes());
+
+            if let ReturnOptions::IntoType(output) = return_options {
+                if output.get_type_symbol() != pointer_type {
+                    return Err(incorrect_type_error(
+                        &[output.get_type_symbol()],
+                        &[pointer_type],
+                    ));
+                }
+                CopyInstruction::new_alloc(
+                    program_memory,
+                    &pointer_value,
+                    output.get_address(),
+                    DIRECT_ADDRESS_BYTES,
+                );
+                return Ok(None);
+            }
+
+            if let ReturnOptions::OneOfTypes(types) = return_options {
+                if !types.is_empty() && !types.iter().any(|t| *t == pointer_type) {
+                    return Err(incorrect_type_error(types, &[pointer_type]));
+                }
+            }
+
+            let mut pointer = TypeFactory::get_unallocated_type(&pointer_type)?;
+            pointer.allocate_variable(stack_sizes, program_memory)?;
+            CopyInstruction::new_alloc(
+                program_memory,
+                &pointer_value,
+                pointer.get_address(),
+                DIRECT_ADDRESS_BYTES,
+            );
+            Ok(Some(RefOrBox::from_box(pointer)))
+        }
+        Operator::Dereference => {
+            let pointer = handle_single_symbol(
+                operand,
+                &ReturnOptions::AnyType,
+                program_memory,
+                reference_stack,
+                stack_sizes,
+            )?
+            .unwrap();
+            let pointer = pointer.as_ref();
+
+            let Some(target) = pointer.get_type_symbol().get_pointer_target() else {
+                return Err(format!(
+                    "{} can only be applied to a typed pointer e.g. ptr<int>, received {}",
+                    operator,
+                    pointer.get_type_symbol()
+                ));
+            };
+
+            let mut typed_pointer = TypedPointerType::new(target);
+            typed_pointer.set_address(pointer.get_address().clone());
+            let value = typed_pointer.dereference(program_memory, stack_sizes)?;
+
+            match return_options {
+                ReturnOptions::IntoType(output) => {
+                    output.runtime_copy_from(value.as_ref(), program_memory)?;
+                    Ok(None)
+                }
+                ReturnOptions::OneOfTypes(types)
+                    if !types.is_empty() && !types.iter().any(|t| *t == target) =>
+                {
+                    Err(incorrect_type_error(types, &[target]))
+                }
+                _ => Ok(Some(RefOrBox::from_box(value))),
+            }
+        }
+        Operator::Unwrap => {
+            let optional = handle_single_symbol(
+                operand,
+                &ReturnOptions::AnyType,
+                program_memory,
+                reference_stack,
+                stack_sizes,
+            )?
+            .unwrap();
+            let optional = optional.as_ref();
+
+            let Some(inner) = optional.get_type_symbol().get_optional_inner() else {
+                return Err(format!(
+                    "{} can only be applied to an optional e.g. opt<int>, received {}",
+                    operator,
+                    optional.get_type_symbol()
+                ));
+            };
+
+            let mut optional_type = OptionalType::new(inner)?;
+            optional_type.set_address(optional.get_address().clone());
+            let value = optional_type.unwrap(program_memory)?;
+
+            match return_options {
+                ReturnOptions::IntoType(output) => {
+                    output.runtime_copy_from(value.as_ref(), program_memory)?;
+                    Ok(None)
+                }
+                ReturnOptions::OneOfTypes(types)
+                    if !types.is_empty() && !types.iter().any(|t| *t == inner) =>
+                {
+                    Err(incorrect_type_error(types, &[inner]))
+                }
+                _ => Ok(Some(RefOrBox::from_box(value))),
+            }
+        }
+        _ => panic!("Not a pointer or optional operator"),
+    }
+}
+
+fn handle_enum_construction<'a>(
+    name: &[String],
+    payload: Option<&[Symbol]>,
+    return_options: &ReturnOptions,
+    program_memory: &mut MemoryManager,
+    reference_stack: &ReferenceStack,
+    stack_sizes: &mut StackSizes,
+) -> Result<Option<RefOrBox<'a, dyn Type + 'a>>, String> {
+    let definition = reference_stack
+        .get_reference(&name[..name.len() - 1])?
+        .get_enum_ref()?
+        .clone();
+    let variant = name.last().unwrap();
+
+    let Some((_, payload_type)) = definition.get_variant(variant) else {
+        return Err(format!(
+            "'{}' is not a variant of enum '{}'",
+            variant, definition.name
+        ));
+    };
+
+    match (payload_type, payload) {
+        (Some(payload_type), None) => {
+            return Err(format!(
+                "Variant '{}' must be given a {} payload e.g. {}([Value])",
+                variant,
+                payload_type,
+                name.join(".")
+            ))
+        }
+        (None, Some(_)) => {
+            return Err(format!("Variant '{}' does not have a payload", variant));
+        }
+        _ => {}
+    }
+
+    let mut value = EnumType::new(definition);
+    let value_type = value.get_type_symbol();
+
+    let into_output = match return_options {
+        ReturnOptions::IntoType(output) => {
+            if output.get_type_symbol() != value_type {
+                return Err(incorrect_type_error(
+                    &[output.get_type_symbol()],
+                    &[value_type],
+                ));
+            }
+            value.set_address(output.get_address().clone());
+            true
+        }
+        ReturnOptions::OneOfTypes(types)
+            if !types.is_empty() && !types.iter().any(|t| *t == value_type) =>
+        {
+            return Err(incorrect_type_error(types, &[value_type]));
+        }
+        _ => {
+            value.allocate_variable(stack_sizes, program_memory)?;
+            false
+        }
+    };
+
+    value.set_variant(variant, program_memory)?;
+
+    if let Some(payload) = payload {
+        let payload_view = value.get_payload_view(variant)?.unwrap();
+        evaluate_arithmetic_into_type(
+            payload,
+            payload_view.as_ref(),
+            program_memory,
+            reference_stack,
+            stack_sizes,
+        )?;
+    }
+
+    if into_output {
+        Ok(None)
+    } else {
+        let value: Box<dyn Type> = bx!(value);
+        Ok(Some(RefOrBox::from_box(value)))
+    }
+}
+
 fn operator_not_implemented_error(
     lhs: &TypeSymbol,
     operator: &Operator,
@@ -375,12 +888,35 @@ fn handle_prefix_operation<'a>(
 ) -> Result<Option<RefOrBox<'a, dyn Type + 'a>>, String> {
     let operand = operand.as_ref();
 
+    // ? Evaluate at compile time if the operand is a constant
+    if let Some(constant) = operand.fold_prefix_operation(operator)? {
+        let result_type = operand.get_prefix_operation_result_type(operator)[0];
+        let mut folded = TypeFactory::get_unallocated_type(&result_type)?;
+        folded.set_address(constant);
+        return return_value(RefOrBox::from_box(folded), return_options, program_memory);
+    }
+
     match return_options {
         ReturnOptions::IntoType(output) => {
             //, run_before_last_step, offset) => {
             // if let Some(f) = run_before_last_step {
             //     f(program_memory, stack_sizes);
             // }
+            let return_types = operand.get_prefix_operation_result_type(operator);
+            if return_types.is_empty() {
+                return Err(operator_not_implemented_error(
+                    &operand.get_type_symbol(),
+                    operator,
+                    None,
+                ));
+            }
+            if !return_types.contains(&output.get_type_symbol()) {
+                return Err(incorrect_type_error(
+                    &[output.get_type_symbol()],
+                    &return_types,
+                ));
+            }
+
             operand.operate_prefix(operator, *output, program_memory, stack_sizes)?;
             Ok(None)
         }
@@ -433,6 +969,84 @@ fn handle_prefix_operation<'a>(
     }
 }
 
+/// Handles a boolean `&` or `|`, skipping the evaluation of `rhs` (and any side effects it has)
+/// if `lhs` already decides the result
+fn handle_short_circuit<'a>(
+    operator: &Operator,
+    lhs: RefOrBox<'a, dyn Type + 'a>,
+    rhs: &Symbol,
+    return_options: &ReturnOptions,
+    program_memory: &mut MemoryManager,
+    reference_stack: &'a ReferenceStack,
+    stack_sizes: &mut StackSizes,
+) -> Result<Option<RefOrBox<'a, dyn Type + 'a>>, String> {
+    let lhs = lhs.as_ref();
+    let short_circuit_value = *operator == Operator::Or;
+
+    // ? Decide at compile time if lhs is a constant
+    if let Address::Immediate(data) = lhs.get_address() {
+        let lhs_value = data[0] != BOOL_FALSE;
+        if lhs_value == short_circuit_value {
+            // ? rhs is still resolved and type-checked but its code is discarded
+            handle_single_symbol(
+                rhs,
+                &ReturnOptions::OneOfTypes(&[TypeSymbol::Boolean]),
+                &mut MemoryManager::new(),
+                reference_stack,
+                stack_sizes,
+            )?;
+
+            let mut result = BoolType::new();
+            result.set_address(Address::Immediate(vec![data[0]]));
+            let result: Box<dyn Type> = bx!(result);
+            return return_value(RefOrBox::from_box(result), return_options, program_memory);
+        }
+
+        let rhs = handle_single_symbol(
+            rhs,
+            &ReturnOptions::OneOfTypes(&[TypeSymbol::Boolean]),
+            program_memory,
+            reference_stack,
+            stack_sizes,
+        )?
+        .unwrap();
+        return return_value(rhs, return_options, program_memory);
+    }
+
+    // ? Use a new variable in case rhs refers to the destination
+    let mut result = BoolType::new();
+    result.allocate_variable(stack_sizes, program_memory)?;
+    result.runtime_copy_from(lhs, program_memory)?;
+
+    // ? Skip rhs if lhs is false for & or true for |
+    let mut skip_instruction = if short_circuit_value {
+        let mut inverted = BoolType::new();
+        inverted.allocate_variable(stack_sizes, program_memory)?;
+        BinaryNotInstruction::new_alloc(
+            program_memory,
+            result.get_address(),
+            inverted.get_address(),
+            BOOLEAN_SIZE,
+        );
+        JumpIfNotInstruction::new_alloc(program_memory, inverted.get_address(), 0)
+    } else {
+        JumpIfNotInstruction::new_alloc(program_memory, result.get_address(), 0)
+    };
+
+    handle_single_symbol(
+        rhs,
+        &ReturnOptions::IntoType(&result),
+        program_memory,
+        reference_stack,
+        stack_sizes,
+    )?;
+
+    skip_instruction.set_destination(program_memory.get_position(), program_memory);
+
+    let result: Box<dyn Type> = bx!(result);
+    return_value(RefOrBox::from_box(result), return_options, program_memory)
+}
+
 fn handle_operation<'a>(
     operator: &Operator,
     lhs: RefOrBox<'a, dyn Type + 'a>,
@@ -444,12 +1058,35 @@ fn handle_operation<'a>(
     let lhs = lhs.as_ref();
     let rhs = rhs.as_ref();
 
+    // ? Evaluate at compile time if both operands are constants
+    if let Some(constant) = lhs.fold_operation(operator, rhs)? {
+        let result_type = lhs.get_operation_result_type(operator, &rhs.get_type_symbol())[0];
+        let mut folded = TypeFactory::get_unallocated_type(&result_type)?;
+        folded.set_address(constant);
+        return return_value(RefOrBox::from_box(folded), return_options, program_memory);
+    }
+
     match return_options {
         ReturnOptions::IntoType(output) => {
             // , run_before_last_step, offset) => {
             // if let Some(f) = run_before_last_step {
             //     f(program_memory, stack_sizes);
             // }
+            let return_types = lhs.get_operation_result_type(operator, &rhs.get_type_symbol());
+            if return_types.is_empty() {
+                return Err(operator_not_implemented_error(
+                    &lhs.get_type_symbol(),
+                    operator,
+                    Some(&rhs.get_type_symbol()),
+                ));
+            }
+            if !return_types.contains(&output.get_type_symbol()) {
+                return Err(incorrect_type_error(
+                    &[output.get_type_symbol()],
+                    &return_types,
+                ));
+            }
+
             lhs.operate(operator, rhs, *output, program_memory, stack_sizes)?;
             Ok(None)
         }
@@ -514,6 +1151,59 @@ fn handle_operation<'a>(
     }
 }
 
+/// Copies `value` into `destination` for a cast, converting between integer widths and
+/// between integers and floats
+fn cast_copy(
+    destination: &dyn Type,
+    value: &dyn Type,
+    program_memory: &mut MemoryManager,
+) -> Result<(), String> {
+    let destination_symbol = destination.get_type_symbol();
+    let value_symbol = value.get_type_symbol();
+    let destination_is_integer = destination_symbol.get_integer_format().is_some();
+    let value_is_integer = value_symbol.get_integer_format().is_some();
+
+    if destination_is_integer && value_is_integer {
+        let mut integer = IntegerType::new(destination_symbol);
+        integer.set_address(destination.get_address().clone());
+        return integer.convert_from(value, program_memory);
+    }
+
+    if destination_symbol == TypeSymbol::Float && value_is_integer {
+        let mut float = FloatType::new();
+        float.set_address(destination.get_address().clone());
+        return float.convert_from(value, program_memory);
+    }
+
+    if value_symbol == TypeSymbol::Float && destination_is_integer {
+        let mut float = FloatType::new();
+        float.set_address(value.get_address().clone());
+        return float.convert_into(destination, program_memory);
+    }
+
+    destination.runtime_copy_from(value, program_memory)?;
+    Ok(())
+}
+
+/// Converts a literal's value for a cast so that it can be used as a constant of `type_symbol`
+fn cast_literal(literal: &Literal, type_symbol: &TypeSymbol) -> Result<Literal, String> {
+    let is_integer = type_symbol.get_integer_format().is_some();
+
+    Ok(match literal {
+        Literal::Float(value) if is_integer => {
+            if !value.is_finite() {
+                return Err(format!("Can't cast {} to {}", value, type_symbol));
+            }
+            // ? Rounds towards zero - out of range values are caught by the integer type
+            Literal::Int(value.trunc() as i128)
+        }
+        Literal::TypedInt(value, _) if is_integer || *type_symbol == TypeSymbol::Float => {
+            Literal::Int(*value)
+        }
+        other => other.clone(),
+    })
+}
+
 fn handle_casting<'a>(
     symbol: &Symbol,
     type_symbol: &TypeSymbol,
@@ -524,6 +1214,8 @@ fn handle_casting<'a>(
 ) -> Result<Option<RefOrBox<'a, dyn Type + 'a>>, String> {
     match symbol {
         Symbol::Literal(literal) => {
+            let literal = &cast_literal(literal, type_symbol)?;
+
             // ? Ignore cast if going into correct type
             if let ReturnOptions::IntoType(output) = return_options {
                 if output.get_type_symbol() == *type_symbol {
@@ -532,27 +1224,22 @@ fn handle_casting<'a>(
                 }
             }
 
-            let mut new_type = TypeFactory::get_unallocated_type(type_symbol)?;
-            new_type.allocate_variable(stack_sizes, program_memory)?;
-            new_type.runtime_copy_from_literal(literal, program_memory)?;
+            // ? Cast literals are folded into an immediate of the target type
+            let mut constant = TypeFactory::get_unallocated_type(type_symbol)?;
+            constant.set_address(constant.get_constant(literal)?);
 
             match return_options {
                 ReturnOptions::IntoType(output) => {
-                    output.runtime_copy_from(new_type.as_ref(), program_memory)?;
+                    output.runtime_copy_from(constant.as_ref(), program_memory)?;
                     Ok(None)
                 }
-                ReturnOptions::OneOfTypes(return_types) => {
-                    if return_types.iter().any(|t| *t == *type_symbol) {
-                        Ok(Some(RefOrBox::from_box(new_type)))
-                    } else {
-                        let return_type = TypeFactory::get_unallocated_type(&return_types[0])?;
-                        return_type.runtime_copy_from(new_type.as_ref(), program_memory)?;
-                        Ok(Some(RefOrBox::from_box(return_type)))
-                    }
-                }
-                ReturnOptions::AnyType | ReturnOptions::PreferType(_) => {
-                    Ok(Some(RefOrBox::from_box(new_type)))
+                ReturnOptions::OneOfTypes(return_types)
+                    if !return_types.is_empty()
+                        && !return_types.iter().any(|t| t == type_symbol) =>
+                {
+                    Err(incorrect_type_error(return_types, &[*type_symbol]))
                 }
+                _ => Ok(Some(RefOrBox::from_box(constant))),
             }
         }
         _ => {
@@ -567,14 +1254,14 @@ fn handle_casting<'a>(
             // ? Ignore cast if going into correct type
             if let ReturnOptions::IntoType(output) = return_options {
                 if output.get_type_symbol() == *type_symbol {
-                    output.runtime_copy_from(value, program_memory)?;
+                    cast_copy(*output, value, program_memory)?;
                     return Ok(None);
                 }
             }
 
             let mut new_type = TypeFactory::get_unallocated_type(type_symbol)?;
             new_type.allocate_variable(stack_sizes, program_memory)?;
-            new_type.runtime_copy_from(value, program_memory)?;
+            cast_copy(new_type.as_ref(), value, program_memory)?;
 
             match return_options {
                 ReturnOptions::IntoType(output) => {
@@ -0,0 +1,108 @@
+use crate::bx;
+use crate::memory::MemoryManager;
+use crate::processing::blocks::{BlockHandler, BlockType, StackSizes};
+use crate::processing::reference_manager::{Reference, ReferenceStack};
+use crate::processing::symbols::{Block, Symbol};
+use crate::processing::types::enumeration::EnumDefinition;
+
+pub struct EnumBlock {
+    definition: Option<EnumDefinition>,
+}
+
+impl EnumBlock {
+    pub fn new_block() -> Box<dyn BlockHandler> {
+        bx!(Self { definition: None })
+    }
+}
+
+impl BlockHandler for EnumBlock {
+    fn get_block_type(&self) -> BlockType {
+        BlockType::Enum
+    }
+
+    fn on_entry(
+        &mut self,
+        _program_memory: &mut MemoryManager,
+        _reference_stack: &mut ReferenceStack,
+        _stack_sizes: &mut StackSizes,
+        symbol_line: &[Symbol],
+    ) -> Result<(), String> {
+        fn declaration_error() -> Result<(), String> {
+            Err(format!(
+                "Enum declaration must be formatted {} [Name]",
+                Block::Enum.get_code_representation()
+            ))
+        }
+
+        if symbol_line.len() != 2 {
+            return declaration_error();
+        }
+
+        let name = match &symbol_line[1] {
+            Symbol::Name(name) => {
+                if name.len() != 1 {
+                    return Err("Enum names cannot have separators".to_string());
+                }
+                name[0].clone()
+            }
+            _ => return declaration_error(),
+        };
+
+        self.definition = Some(EnumDefinition::new(name));
+
+        Ok(())
+    }
+
+    fn on_forced_exit(
+        &mut self,
+        _program_memory: &mut MemoryManager,
+        reference_stack: &mut ReferenceStack,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        let definition = self.definition.take().unwrap();
+
+        if definition.variants.is_empty() {
+            return Err(format!(
+                "Enum '{}' must declare at least one variant",
+                definition.name
+            ));
+        }
+
+        let name = vec![definition.name.clone()];
+        reference_stack.register_reference(Reference::Enum(definition), name)?;
+        Ok(())
+    }
+
+    fn update_sub_block(&mut self, _block_type: Option<BlockType>) -> Result<(), String> {
+        Err("Enums can only contain variants".to_string())
+    }
+
+    fn handle_line(&mut self, line: &[Symbol]) -> Result<(), String> {
+        fn variant_error() -> Result<(), String> {
+            Err("Enum variants must be formatted [Name] or [Name]([Type])".to_string())
+        }
+
+        let name = match line.first() {
+            Some(Symbol::Name(name)) => Symbol::get_name_string(name)?,
+            _ => return variant_error(),
+        };
+
+        let payload = match line.get(1) {
+            None => None,
+            Some(Symbol::BracketedSection(payload)) => match payload.as_slice() {
+                [Symbol::Type(type_symbol)] => Some(*type_symbol),
+                _ => return variant_error(),
+            },
+            Some(_) => return variant_error(),
+        };
+
+        if line.len() > 2 {
+            return variant_error();
+        }
+
+        self.definition
+            .as_mut()
+            .unwrap()
+            .add_variant(name, payload)
+    }
+}
@@ -8,9 +8,11 @@ use crate::processing::instructions::stack_up_1::StackUpInstruction;
 use crate::processing::lines::variable_initialisation::VariableInitialisationLine;
 use crate::processing::reference_manager::function::FunctionReference;
 use crate::processing::reference_manager::{Reference, ReferenceStack};
-use crate::processing::symbols::{Block, Symbol, CLASS_SELF_NAME};
+use crate::processing::symbols::{
+    Block, Keyword, Symbol, TypeSymbol, CLASS_SELF_NAME, RETURN_ADDRESS_NAME, RETURN_VALUE_NAME,
+};
 use crate::processing::types::pointer::PointerType;
-use crate::processing::types::Type;
+use crate::processing::types::{Type, TypeFactory};
 
 pub struct FunctionBlock {
     name: Option<Vec<String>>,
@@ -20,6 +22,8 @@ pub struct FunctionBlock {
     stack_create_instruction: Option<StackCreateInstruction>,
     return_pointer: Option<PointerType>,
     stack_size_insertion_addresses: Vec<usize>,
+    /// Whether the previous line of the body was a return
+    returned: bool,
 }
 
 impl FunctionBlock {
@@ -32,13 +36,32 @@ impl FunctionBlock {
             stack_create_instruction: None,
             return_pointer: None,
             stack_size_insertion_addresses: Vec::new(),
+            returned: false,
         })
     }
+
+    /// Name of the hidden reference recording a function's return type. Names can't contain
+    /// spaces so this can't clash with a variable
+    fn get_return_type_name(function_name: &[String]) -> Vec<String> {
+        vec![format!("{} {}", function_name.join("."), RETURN_VALUE_NAME)]
+    }
+
+    /// Returns the type a function returns, or `None` if it doesn't return a value
+    pub fn get_return_type(
+        function_name: &[String],
+        reference_stack: &ReferenceStack,
+    ) -> Option<TypeSymbol> {
+        reference_stack
+            .get_reference(&FunctionBlock::get_return_type_name(function_name))
+            .and_then(|reference| reference.get_variable_ref())
+            .ok()
+            .map(|return_type| return_type.get_type_symbol())
+    }
 }
 
 /*
     - Reference stack (1) created automatically
-    - Add parameters as references
+    - Add the hidden return value pointer (if there is a return type) and parameters as references
     - Add reference stack (2)
     - (function body)
     - Remove reference stack (2)
@@ -81,12 +104,12 @@ impl BlockHandler for FunctionBlock {
 
         fn declaration_error() -> Result<(), String> {
             Err(format!(
-                "Function declaration must be formatted {} [Name] [Parameter List]",
+                "Function declaration must be formatted {0} [Name] [Parameter List] or {0} [Name] [Parameter List] [Return Type]",
                 Block::Function.get_code_representation()
             ))
         }
 
-        if symbol_line.len() != 3 {
+        if symbol_line.len() != 3 && symbol_line.len() != 4 {
             return declaration_error();
         }
 
@@ -103,6 +126,19 @@ impl BlockHandler for FunctionBlock {
             _ => return declaration_error(),
         });
 
+        //? Return type e.g. fn f(int a) (int, bool)
+        let return_type = match symbol_line.get(3) {
+            None => None,
+            Some(Symbol::Type(return_type)) => Some(*return_type),
+            Some(Symbol::List(list)) => match TypeSymbol::from_type_list(list) {
+                Some(return_type) => Some(return_type),
+                None => {
+                    return Err("Tuple types must be a list of types e.g. (int, bool)".to_string())
+                }
+            },
+            Some(_) => return declaration_error(),
+        };
+
         //? Save previous reference limit and apply new
         self.previous_reference_limit = Some(reference_stack.get_reference_depth_limit());
 
@@ -124,10 +160,26 @@ impl BlockHandler for FunctionBlock {
             reference_stack.set_reference_depth_limit(reference_stack.get_depth());
         }
 
+        if return_type.is_some() && self.name.as_ref().unwrap().len() > 1 {
+            return Err("Functions in a class can't return values".to_string());
+        }
+
         let mut return_pointer = PointerType::new();
         return_pointer.allocate_variable(stack_sizes, program_memory)?;
         self.return_pointer = Some(return_pointer);
 
+        //? The caller passes a pointer to where the return value should be written as a hidden
+        //? first parameter
+        if let Some(return_type) = return_type {
+            let mut return_value =
+                TypeFactory::get_unallocated_type(&TypeSymbol::pointer_to(return_type))?;
+            return_value.allocate_variable(stack_sizes, program_memory)?;
+            reference_stack.register_reference(
+                Reference::Variable(return_value),
+                vec![RETURN_VALUE_NAME.to_string()],
+            )?;
+        }
+
         let parameter_list = match &symbol_line[2] {
             Symbol::List(parameters) => parameters,
             _ => return declaration_error(),
@@ -168,6 +220,13 @@ impl BlockHandler for FunctionBlock {
         //     }
         // }
 
+        //? Lets return lines jump back - registered after the parameters are collected as it isn't
+        //? passed by the caller
+        reference_stack.register_reference(
+            Reference::Variable(bx!(self.return_pointer.as_ref().unwrap().duplicate_known())),
+            vec![RETURN_ADDRESS_NAME.to_string()],
+        )?;
+
         //? Register function reference
         let function_reference = FunctionReference::new(
             self.start_position.unwrap(),
@@ -181,9 +240,18 @@ impl BlockHandler for FunctionBlock {
         } else {
             reference_stack.register_reference_with_offset(
                 Reference::Function(function_reference),
-                name,
+                name.clone(),
                 1,
             )?;
+
+            //? Lets callers find out what (if anything) the function returns
+            if let Some(return_type) = return_type {
+                reference_stack.register_reference_with_offset(
+                    Reference::Variable(TypeFactory::get_unallocated_type(&return_type)?),
+                    FunctionBlock::get_return_type_name(&name),
+                    1,
+                )?;
+            }
         }
 
         //? Add new stack to separate parameters from function body
@@ -257,4 +325,16 @@ impl BlockHandler for FunctionBlock {
     fn on_continue(&mut self, _program_memory: &mut MemoryManager) -> Result<bool, String> {
         Err("Can't continue a function".to_string())
     }
+
+    //? Only called for lines directly in the body, so returns in nested blocks aren't seen here
+    fn handle_line(&mut self, line: &[Symbol]) -> Result<(), String> {
+        if std::mem::take(&mut self.returned) {
+            return Err(format!(
+                "Any code following '{}' will never be run",
+                Keyword::Return.get_code_representation()
+            ));
+        }
+        self.returned = matches!(line.first(), Some(Symbol::Keyword(Keyword::Return)));
+        Ok(())
+    }
 }
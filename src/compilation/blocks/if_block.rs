@@ -88,22 +88,14 @@ impl BlockHandler for IfBlock {
         //? Handle elif or else
         match block_type {
             Block::Elif => {
-                if self.jump_next_instruction.is_none() {
-                    return Err(format!(
-                        "{} cannot follow an {} block as it will never be reached",
-                        Block::Elif,
-                        Block::Else
-                    ));
-                }
-
                 // Add instruction to skip to end if previous if/elif condition was met and executed
                 self.jump_end_instructions
                     .push(JumpInstruction::new_alloc(program_memory, 0));
                 // Set jump next instruction to jump to this section (check this block if previous was false)
-                self.jump_next_instruction
-                    .as_mut()
-                    .unwrap()
-                    .set_destination(program_memory.get_position(), program_memory);
+                //? Sections after an else are never reached - reported by the linter
+                if let Some(instruction) = self.jump_next_instruction.as_mut() {
+                    instruction.set_destination(program_memory.get_position(), program_memory);
+                }
                 // Reuse if handling
                 self.on_entry(program_memory, reference_stack, stack_sizes, symbol_line)?;
                 // Create new scope
@@ -115,20 +107,13 @@ impl BlockHandler for IfBlock {
                 if symbol_line.len() > 1 {
                     return Err("Else cannot be followed by any other symbol".to_string());
                 }
-                if self.jump_next_instruction.is_none() {
-                    return Err(
-                        "'else' cannot follow an 'else' block as it will never be reached"
-                            .to_string(),
-                    );
-                }
                 // Add instruction to skip to end if previous if/elif condition was met and executed
                 self.jump_end_instructions
                     .push(JumpInstruction::new_alloc(program_memory, 0));
                 // Set jump next instruction to jump to this section (run this block if previous was false)
-                self.jump_next_instruction
-                    .as_mut()
-                    .unwrap()
-                    .set_destination(program_memory.get_position(), program_memory);
+                if let Some(instruction) = self.jump_next_instruction.as_mut() {
+                    instruction.set_destination(program_memory.get_position(), program_memory);
+                }
                 // Else block cannot be skipped
                 self.jump_next_instruction = None;
                 // Create new scope
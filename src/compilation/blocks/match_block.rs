@@ -0,0 +1,295 @@
+use crate::bx;
+use crate::memory::MemoryManager;
+use crate::processing::arithmetic::evaluate_arithmetic_to_any_type;
+use crate::processing::blocks::StackSizes;
+use crate::processing::blocks::{BlockHandler, BlockType};
+use crate::processing::instructions::jump_if_not_9::JumpIfNotInstruction;
+use crate::processing::instructions::jump_instruction_10::JumpInstruction;
+use crate::processing::reference_manager::{Reference, ReferenceStack};
+use crate::processing::symbols::{Block, Symbol};
+use crate::processing::types::boolean::BoolType;
+use crate::processing::types::enumeration::{EnumDefinition, EnumType};
+use crate::processing::types::Type;
+
+pub struct MatchBlock {
+    subject: Option<Box<dyn Type>>,
+    definition: Option<EnumDefinition>,
+    covered_variants: Vec<String>,
+    has_else: bool,
+    jump_next_instruction: Option<JumpIfNotInstruction>,
+    jump_end_instructions: Vec<JumpInstruction>,
+}
+
+impl MatchBlock {
+    pub fn new_block() -> Box<dyn BlockHandler> {
+        bx!(Self {
+            subject: None,
+            definition: None,
+            covered_variants: Vec::new(),
+            has_else: false,
+            jump_next_instruction: None,
+            jump_end_instructions: Vec::new(),
+        })
+    }
+
+    fn case_error() -> String {
+        format!(
+            "Case must be formatted {} [Enum].[Variant] or {} [Enum].[Variant] [Name]",
+            Block::Case.get_code_representation(),
+            Block::Case.get_code_representation()
+        )
+    }
+
+    /// Ends the previous case (if any) so that it jumps past the rest of the match
+    fn end_previous_case(&mut self, program_memory: &mut MemoryManager) {
+        if self.jump_next_instruction.is_none() {
+            return;
+        }
+
+        // Add instruction to skip to end if previous case was matched and executed
+        self.jump_end_instructions
+            .push(JumpInstruction::new_alloc(program_memory, 0));
+        // Set jump next instruction to jump to this case (check this case if previous didn't match)
+        self.jump_next_instruction
+            .as_mut()
+            .unwrap()
+            .set_destination(program_memory.get_position(), program_memory);
+    }
+
+    fn handle_case(
+        &mut self,
+        program_memory: &mut MemoryManager,
+        reference_stack: &mut ReferenceStack,
+        stack_sizes: &mut StackSizes,
+        symbol_line: &[Symbol],
+    ) -> Result<(), String> {
+        if self.has_else {
+            return Err(format!(
+                "{} cannot follow an {} block as it will never be reached",
+                Block::Case,
+                Block::Else
+            ));
+        }
+
+        if symbol_line.len() != 2 && symbol_line.len() != 3 {
+            return Err(Self::case_error());
+        }
+
+        let (enum_name, variant) = match &symbol_line[1] {
+            Symbol::Name(name) if name.len() >= 2 => (
+                Vec::from(&name[..name.len() - 1]),
+                name.last().unwrap().clone(),
+            ),
+            _ => return Err(Self::case_error()),
+        };
+
+        let binding = match symbol_line.get(2) {
+            None => None,
+            Some(Symbol::Name(name)) => Some(name.clone()),
+            Some(_) => return Err(Self::case_error()),
+        };
+
+        //? Every case must be a variant of the subject's own enum
+        let definition = self.definition.as_ref().unwrap();
+        if enum_name.len() != 1 || enum_name[0] != definition.name {
+            return Err(format!(
+                "Case '{}.{}' is not a variant of the matched enum '{}'",
+                enum_name.join("."),
+                variant,
+                definition.name
+            ));
+        }
+        if definition.get_variant(&variant).is_none() {
+            return Err(format!(
+                "'{}' is not a variant of enum '{}'",
+                variant, definition.name
+            ));
+        }
+
+        if self.covered_variants.contains(&variant) {
+            return Err(format!(
+                "Case '{}' has already been matched and will never be reached",
+                variant
+            ));
+        }
+
+        let mut subject = EnumType::new(definition.clone());
+        subject.set_address(self.subject.as_ref().unwrap().get_address().clone());
+
+        self.end_previous_case(program_memory);
+
+        //? Insert instruction to skip this case if the tag doesn't match
+        let mut condition_boolean = BoolType::new();
+        condition_boolean.allocate_variable(stack_sizes, program_memory)?;
+        subject.is_variant(&variant, &condition_boolean, program_memory)?;
+        self.jump_next_instruction = Some(JumpIfNotInstruction::new_alloc(
+            program_memory,
+            condition_boolean.get_address(),
+            0,
+        ));
+
+        // Create new scope
+        reference_stack.remove_handler();
+        reference_stack.add_handler();
+
+        //? Bind the payload - the binding refers to the matched value's payload directly
+        if let Some(binding) = binding {
+            let Some(payload) = subject.get_payload_view(&variant)? else {
+                return Err(format!("Variant '{}' has no payload to bind", variant));
+            };
+            reference_stack.register_reference(Reference::Variable(payload), binding)?;
+        }
+
+        self.covered_variants.push(variant);
+
+        Ok(())
+    }
+}
+
+impl BlockHandler for MatchBlock {
+    fn get_block_type(&self) -> BlockType {
+        BlockType::Match
+    }
+
+    fn on_entry(
+        &mut self,
+        program_memory: &mut MemoryManager,
+        reference_stack: &mut ReferenceStack,
+        stack_sizes: &mut StackSizes,
+        symbol_line: &[Symbol],
+    ) -> Result<(), String> {
+        if symbol_line.len() < 2 {
+            return Err(format!(
+                "Match statement must be formatted {} [Value]",
+                Block::Match.get_code_representation()
+            ));
+        }
+
+        //? Evaluate the value being matched on
+        let result = evaluate_arithmetic_to_any_type(
+            &symbol_line[1..],
+            program_memory,
+            reference_stack,
+            stack_sizes,
+        )?;
+        let subject = result.as_ref();
+
+        let Some(enum_name) = subject.get_type_symbol().get_enum_name() else {
+            return Err(format!(
+                "Can only match on enum values, received {}",
+                subject.get_type_symbol()
+            ));
+        };
+
+        //? Cases are checked against the subject's definition, not the first case's
+        let definition = reference_stack
+            .get_reference(&[enum_name.to_string()])?
+            .get_enum_ref()?
+            .clone();
+
+        self.subject = Some(subject.duplicate());
+        self.definition = Some(definition);
+
+        Ok(())
+    }
+
+    fn on_exit(
+        &mut self,
+        program_memory: &mut MemoryManager,
+        reference_stack: &mut ReferenceStack,
+        stack_sizes: &mut StackSizes,
+        symbol_line: &[Symbol],
+    ) -> Result<bool, String> {
+        fn exit_with_cleanup(
+            this: &mut MatchBlock,
+            program_memory: &mut MemoryManager,
+            reference_stack: &mut ReferenceStack,
+            stack_sizes: &mut StackSizes,
+        ) -> Result<bool, String> {
+            this.on_forced_exit(program_memory, reference_stack, stack_sizes)?;
+            Ok(true)
+        }
+
+        match symbol_line.first() {
+            Some(Symbol::Block(Block::Case)) => {
+                self.handle_case(program_memory, reference_stack, stack_sizes, symbol_line)?;
+                Ok(false)
+            }
+            Some(Symbol::Block(Block::Else)) => {
+                if symbol_line.len() > 1 {
+                    return Err("Else cannot be followed by any other symbol".to_string());
+                }
+                if self.has_else {
+                    return Err(
+                        "'else' cannot follow an 'else' block as it will never be reached"
+                            .to_string(),
+                    );
+                }
+                if self.jump_next_instruction.is_none() {
+                    return Err(format!(
+                        "{} must have a {} before an {}",
+                        Block::Match,
+                        Block::Case,
+                        Block::Else
+                    ));
+                }
+
+                self.end_previous_case(program_memory);
+                // Else block cannot be skipped
+                self.jump_next_instruction = None;
+                self.has_else = true;
+                // Create new scope
+                reference_stack.remove_handler();
+                reference_stack.add_handler();
+                Ok(false)
+            }
+            _ => exit_with_cleanup(self, program_memory, reference_stack, stack_sizes),
+        }
+    }
+
+    fn on_forced_exit(
+        &mut self,
+        program_memory: &mut MemoryManager,
+        _reference_stack: &mut ReferenceStack,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        let definition = self.definition.as_ref().unwrap();
+        if self.covered_variants.is_empty() {
+            return Err(format!(
+                "{} must be followed by at least one {}",
+                Block::Match,
+                Block::Case
+            ));
+        }
+
+        //? Check exhaustiveness
+        if !self.has_else {
+            let missing: Vec<&str> = definition
+                .variants
+                .iter()
+                .map(|(variant, _)| variant.as_str())
+                .filter(|variant| !self.covered_variants.iter().any(|c| c == variant))
+                .collect();
+
+            if !missing.is_empty() {
+                return Err(format!(
+                    "Match on '{}' is not exhaustive - missing case(s) {} (or add an {})",
+                    definition.name,
+                    missing.join(", "),
+                    Block::Else
+                ));
+            }
+        }
+
+        // Set jump to next
+        if let Some(instruction) = self.jump_next_instruction.as_mut() {
+            instruction.set_destination(program_memory.get_position(), program_memory)
+        }
+
+        // Set all jump to end
+        for j in self.jump_end_instructions.iter_mut() {
+            j.set_destination(program_memory.get_position(), program_memory);
+        }
+        Ok(())
+    }
+}
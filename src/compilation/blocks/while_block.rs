@@ -5,13 +5,15 @@ use crate::processing::blocks::{BlockHandler, BlockType, StackSizes};
 use crate::processing::instructions::jump_if_not_9::JumpIfNotInstruction;
 use crate::processing::instructions::jump_instruction_10::JumpInstruction;
 use crate::processing::reference_manager::ReferenceStack;
-use crate::processing::symbols::{Symbol, TypeSymbol};
+use crate::processing::symbols::{Keyword, Symbol, TypeSymbol};
 
 pub struct WhileBlock {
     jump_end_instruction: Option<JumpIfNotInstruction>,
     jump_end_instructions: Vec<JumpInstruction>,
     jump_start_instructions: Vec<JumpInstruction>,
     start_position: Option<usize>,
+    /// The break or continue ending the previous line of the body
+    exited_by: Option<Keyword>,
 }
 
 impl WhileBlock {
@@ -21,6 +23,7 @@ impl WhileBlock {
             jump_end_instructions: Vec::new(),
             jump_start_instructions: Vec::new(),
             start_position: None,
+            exited_by: None,
         })
     }
 }
@@ -107,4 +110,19 @@ impl BlockHandler for WhileBlock {
             .push(JumpInstruction::new_alloc(program_memory, 0));
         Ok(true)
     }
+
+    //? Only called for lines directly in the body, so breaks in nested blocks aren't seen here
+    fn handle_line(&mut self, line: &[Symbol]) -> Result<(), String> {
+        if let Some(keyword) = self.exited_by.take() {
+            return Err(format!(
+                "Any code following '{}' will never be run",
+                keyword.get_code_representation()
+            ));
+        }
+        if let Some(Symbol::Keyword(keyword @ (Keyword::Break | Keyword::Continue))) = line.first()
+        {
+            self.exited_by = Some(*keyword);
+        }
+        Ok(())
+    }
 }
@@ -1,15 +1,162 @@
 use super::LineHandler;
 use crate::memory::MemoryManager;
-use crate::processing::blocks::BlockCoordinator;
+use crate::processing::arithmetic::evaluate_arithmetic_into_type;
+use crate::processing::blocks::function_block::FunctionBlock;
+use crate::processing::blocks::{BlockCoordinator, StackSizes};
 use crate::processing::processor::ProcessingResult;
+use crate::processing::reference_manager::{Reference, ReferenceStack};
 
-use crate::processing::symbols::Symbol;
+use crate::processing::symbols::{Operator, Symbol, RETURN_VALUE_NAME};
+use crate::processing::types::{Type, TypeFactory};
 
 use crate::q;
 use crate::util::must_use_option::MustUseOption;
 
 pub struct CallLine {}
 
+impl CallLine {
+    /// Calls a function, writing the value it returns into `destination`. The value is discarded
+    /// if `destination` is `None`
+    pub fn handle_call(
+        name: &[String],
+        arguments: &[Vec<Symbol>],
+        destination: Option<&dyn Type>,
+        program_memory: &mut MemoryManager,
+        reference_stack: &mut ReferenceStack,
+        stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        let Some(return_type) = FunctionBlock::get_return_type(name, reference_stack) else {
+            if destination.is_some() {
+                return Err(format!("'{}' doesn't return a value", name.join(".")));
+            }
+            return CallLine::call_function(
+                name,
+                arguments,
+                program_memory,
+                reference_stack,
+                stack_sizes,
+            );
+        };
+
+        if let Some(destination) = destination {
+            if destination.get_type_symbol() != return_type {
+                return Err(format!(
+                    "'{}' returns {}, expected {}",
+                    name.join("."),
+                    return_type,
+                    destination.get_type_symbol()
+                ));
+            }
+        }
+
+        //? The function writes its return value through a pointer so it must be stored directly
+        //? e.g. not behind another pointer
+        let direct_destination =
+            destination.filter(|destination| destination.get_address().to_indirect().is_some());
+        let mut return_value = TypeFactory::get_unallocated_type(&return_type)?;
+        match direct_destination {
+            Some(destination) => return_value.set_address(destination.get_address().clone()),
+            None => return_value.allocate_variable(stack_sizes, program_memory)?,
+        }
+
+        //? The pointer is passed as a hidden first argument
+        let mut arguments_with_return = Vec::with_capacity(arguments.len() + 1);
+        arguments_with_return.push(vec![
+            Symbol::Operator(Operator::AddressOf),
+            Symbol::Name(vec![RETURN_VALUE_NAME.to_string()]),
+        ]);
+        arguments_with_return.extend_from_slice(arguments);
+
+        //? Only visible while the call is made
+        reference_stack.add_handler();
+        let result = reference_stack
+            .register_reference(
+                Reference::Variable(return_value.duplicate()),
+                vec![RETURN_VALUE_NAME.to_string()],
+            )
+            .and_then(|_| {
+                CallLine::call_function(
+                    name,
+                    &arguments_with_return,
+                    program_memory,
+                    reference_stack,
+                    stack_sizes,
+                )
+            });
+        reference_stack.remove_handler();
+        result?;
+
+        if let (Some(destination), None) = (destination, direct_destination) {
+            destination.runtime_copy_from(return_value.as_ref(), program_memory)?;
+        }
+
+        Ok(())
+    }
+
+    /// Evaluates `section` into `destination`. Unlike `evaluate_arithmetic_into_type`, `section`
+    /// can be a call to a function that returns a value e.g. `f(1, 2)`
+    pub fn evaluate_into_type(
+        section: &[Symbol],
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        reference_stack: &mut ReferenceStack,
+        stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        //? Calls are handled here rather than in arithmetic as the hidden return argument has to
+        //? be registered on the reference stack
+        match section {
+            [Symbol::Name(name), Symbol::List(arguments)] => CallLine::handle_call(
+                name,
+                arguments,
+                Some(destination),
+                program_memory,
+                reference_stack,
+                stack_sizes,
+            ),
+            _ => evaluate_arithmetic_into_type(
+                section,
+                destination,
+                program_memory,
+                reference_stack,
+                stack_sizes,
+            ),
+        }
+    }
+
+    fn call_function(
+        name: &[String],
+        arguments: &[Vec<Symbol>],
+        program_memory: &mut MemoryManager,
+        reference_stack: &mut ReferenceStack,
+        stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        // let (function_reference, offset) = q!(block_coordinator.get_reference_and_offset(name));
+
+        let function_reference = reference_stack.get_reference(name)?.get_function_ref()?;
+
+        let incomplete_function_call = function_reference.call(
+            None,
+            arguments,
+            program_memory,
+            reference_stack,
+            stack_sizes,
+        )?;
+
+        if let MustUseOption::Some(incomplete_function_call) = incomplete_function_call {
+            reference_stack
+                .get_reference_mut(name)
+                .unwrap()
+                .get_function_mut()
+                .unwrap()
+                .add_incomplete_function_call(incomplete_function_call);
+        }
+
+        // q!(reference_stack.register_reference_with_offset(function_reference, offset));
+
+        Ok(())
+    }
+}
+
 impl LineHandler for CallLine {
     fn process_line(
         line: &[Symbol],
@@ -24,8 +171,9 @@ impl LineHandler for CallLine {
         }
 
         if line.len() > 2 {
-            return ProcessingResult::Failure(
+            return ProcessingResult::FailureAt(
                 "A call can't be followed by anything on the same line".to_string(),
+                2..line.len(),
             );
         }
 
@@ -39,26 +187,17 @@ impl LineHandler for CallLine {
             _ => panic!(),
         };
 
-        // let (function_reference, offset) = q!(block_coordinator.get_reference_and_offset(name));
-
         let (stack_sizes, reference_stack) =
             block_coordinator.get_stack_sizes_and_reference_stack();
 
-        let function_reference = q!(q!(reference_stack.get_reference(name)).get_function_ref());
-
-        let incomplete_function_call =
-            q!(function_reference.call(None, args, program_memory, reference_stack, stack_sizes));
-
-        if let MustUseOption::Some(incomplete_function_call) = incomplete_function_call {
-            reference_stack
-                .get_reference_mut(name)
-                .unwrap()
-                .get_function_mut()
-                .unwrap()
-                .add_incomplete_function_call(incomplete_function_call);
-        }
-
-        // q!(reference_stack.register_reference_with_offset(function_reference, offset));
+        q!(CallLine::handle_call(
+            name,
+            args,
+            None,
+            program_memory,
+            reference_stack,
+            stack_sizes
+        ));
 
         ProcessingResult::Success
     }
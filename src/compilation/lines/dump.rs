@@ -23,8 +23,9 @@ impl LineHandler for DumpLine {
         };
 
         if line.len() > 1 {
-            return ProcessingResult::Failure(
+            return ProcessingResult::FailureAt(
                 "Dump cannot be followed by anything else".to_string(),
+                1..line.len(),
             );
         }
 
@@ -0,0 +1,42 @@
+use crate::memory::MemoryManager;
+
+use crate::processing::blocks::enum_block::EnumBlock;
+use crate::processing::blocks::{BlockCoordinator, BlockType};
+
+use crate::processing::lines::LineHandler;
+use crate::processing::processor::ProcessingResult;
+use crate::processing::symbols::{Block, Symbol};
+use crate::q;
+
+pub struct EnumLine {}
+
+impl LineHandler for EnumLine {
+    fn process_line(
+        line: &[Symbol],
+        program_memory: &mut MemoryManager,
+        block_coordinator: &mut BlockCoordinator,
+    ) -> ProcessingResult {
+        if line.is_empty() {
+            return ProcessingResult::Unmatched;
+        }
+
+        //? Lines inside an enum are variants
+        if matches!(block_coordinator.get_block_handler_type(), BlockType::Enum) {
+            q!(block_coordinator.get_block_handler_mut().handle_line(line));
+            return ProcessingResult::Success;
+        }
+
+        match line[0] {
+            Symbol::Block(Block::Enum) => {
+                q!(block_coordinator.add_block_handler(
+                    EnumBlock::new_block(),
+                    program_memory,
+                    line,
+                ));
+                block_coordinator.skip_sub_block_check = true;
+                ProcessingResult::Success
+            }
+            _ => ProcessingResult::Unmatched,
+        }
+    }
+}
@@ -5,6 +5,7 @@ pub trait LineHandler {
     /// * `ProcessingResult::Successful` if the line is matched
     /// * `ProcessingResult::Unmatched` if the line is unmatched
     /// * `ProcessingResult::Failure(reason)` if the line is matched but an error occurred while processing it
+    /// * `ProcessingResult::FailureAt(reason, symbols)` if the error was caused by the top level symbols in the range
     fn process_line(
         line: &[Symbol],
         program_memory: &mut MemoryManager,
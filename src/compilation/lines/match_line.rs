@@ -0,0 +1,45 @@
+use crate::memory::MemoryManager;
+
+use crate::processing::blocks::match_block::MatchBlock;
+use crate::processing::blocks::BlockCoordinator;
+
+use crate::processing::lines::LineHandler;
+use crate::processing::processor::ProcessingResult;
+use crate::processing::symbols::{Block, Symbol};
+
+pub struct MatchLine {}
+
+impl LineHandler for MatchLine {
+    fn process_line(
+        line: &[Symbol],
+        program_memory: &mut MemoryManager,
+        block_coordinator: &mut BlockCoordinator,
+    ) -> ProcessingResult {
+        if line.is_empty() {
+            return ProcessingResult::Unmatched;
+        }
+
+        match line[0] {
+            Symbol::Block(Block::Match) => {
+                match block_coordinator.add_block_handler(
+                    MatchBlock::new_block(),
+                    program_memory,
+                    line,
+                ) {
+                    Err(e) => ProcessingResult::Failure(e),
+                    Ok(_) => ProcessingResult::Success,
+                }
+            }
+            //? If not intercepted, there was no match
+            Symbol::Block(Block::Case) => ProcessingResult::FailureAt(
+                format!(
+                    "{} can only follow a {} statement",
+                    Block::Case,
+                    Block::Match
+                ),
+                0..1,
+            ),
+            _ => ProcessingResult::Unmatched,
+        }
+    }
+}
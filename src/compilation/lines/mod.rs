@@ -4,8 +4,12 @@ pub mod break_continue;
 pub mod call;
 pub mod class_line;
 pub mod dump;
+pub mod enum_line;
 pub mod function_line;
 pub mod if_line;
+pub mod match_line;
+pub mod print;
+pub mod return_line;
 pub mod variable_assignment;
 pub mod variable_initialisation;
 pub mod view_memory;
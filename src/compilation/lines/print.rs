@@ -0,0 +1,71 @@
+use crate::memory::MemoryManager;
+use crate::processing::arithmetic::evaluate_arithmetic_to_any_type;
+use crate::processing::blocks::BlockCoordinator;
+use crate::processing::instructions::print_24::{PrintFormat, PrintInstruction};
+use crate::processing::lines::LineHandler;
+use crate::processing::processor::ProcessingResult;
+use crate::processing::symbols::{Builtin, Symbol, TypeSymbol};
+use crate::q;
+
+pub struct PrintLine {}
+
+impl LineHandler for PrintLine {
+    fn process_line(
+        line: &[Symbol],
+        program_memory: &mut MemoryManager,
+        block_coordinator: &mut BlockCoordinator,
+    ) -> ProcessingResult {
+        if line.is_empty() {
+            return ProcessingResult::Unmatched;
+        }
+
+        match line[0] {
+            Symbol::Builtin(Builtin::Print) => {}
+            _ => return ProcessingResult::Unmatched,
+        };
+
+        if line.len() < 2 {
+            return ProcessingResult::FailureAt(
+                "print must be followed by a value".to_string(),
+                0..1,
+            );
+        }
+
+        let (stack_sizes, reference_stack) =
+            block_coordinator.get_stack_sizes_and_reference_stack();
+
+        let value = q!(evaluate_arithmetic_to_any_type(
+            &line[1..],
+            program_memory,
+            reference_stack,
+            stack_sizes
+        ));
+        let value = value.as_ref();
+
+        let format = match value.get_type_symbol() {
+            TypeSymbol::Float => PrintFormat::Float,
+            TypeSymbol::Boolean => PrintFormat::Boolean,
+            TypeSymbol::Character => PrintFormat::Character,
+            TypeSymbol::Pointer => PrintFormat::UnsignedInteger,
+            t => match t.get_integer_format() {
+                Some((_, true)) => PrintFormat::SignedInteger,
+                Some((_, false)) => PrintFormat::UnsignedInteger,
+                None => {
+                    return ProcessingResult::FailureAt(
+                        format!("{} values cannot be printed", t),
+                        1..line.len(),
+                    )
+                }
+            },
+        };
+
+        PrintInstruction::new_alloc(
+            program_memory,
+            value.get_address(),
+            value.get_length(),
+            format,
+        );
+
+        ProcessingResult::Success
+    }
+}
@@ -0,0 +1,90 @@
+use crate::memory::MemoryManager;
+use crate::processing::blocks::BlockCoordinator;
+use crate::processing::instructions::dynamic_jump_11::DynamicJumpInstruction;
+use crate::processing::lines::call::CallLine;
+use crate::processing::lines::LineHandler;
+use crate::processing::processor::ProcessingResult;
+use crate::processing::symbols::{Keyword, Symbol, RETURN_ADDRESS_NAME, RETURN_VALUE_NAME};
+use crate::processing::types::typed_pointer::TypedPointerType;
+use crate::processing::types::Type;
+use crate::q;
+
+pub struct ReturnLine {}
+
+impl LineHandler for ReturnLine {
+    fn process_line(
+        line: &[Symbol],
+        program_memory: &mut MemoryManager,
+        block_coordinator: &mut BlockCoordinator,
+    ) -> ProcessingResult {
+        match line.first() {
+            Some(Symbol::Keyword(Keyword::Return)) => {}
+            _ => return ProcessingResult::Unmatched,
+        };
+
+        let (stack_sizes, reference_stack) =
+            block_coordinator.get_stack_sizes_and_reference_stack();
+
+        //? Registered by the function block
+        let Ok(return_address) = reference_stack.get_reference(&[RETURN_ADDRESS_NAME.to_string()])
+        else {
+            return ProcessingResult::FailureAt(
+                format!(
+                    "{} can only be used in a function",
+                    Keyword::Return.get_code_representation()
+                ),
+                0..1,
+            );
+        };
+        let return_address = q!(return_address.get_variable_ref()).duplicate();
+
+        //? Only functions with a return type have the hidden return value pointer
+        let return_value = reference_stack
+            .get_reference(&[RETURN_VALUE_NAME.to_string()])
+            .ok()
+            .map(|pointer| {
+                pointer
+                    .get_variable_ref()
+                    .map(|pointer| pointer.duplicate())
+            });
+        let return_value = q!(return_value.transpose());
+
+        match (return_value, line.len() > 1) {
+            (None, false) => {}
+            (None, true) => {
+                return ProcessingResult::FailureAt(
+                    "This function doesn't return a value".to_string(),
+                    1..line.len(),
+                )
+            }
+            (Some(pointer), false) => {
+                return ProcessingResult::FailureAt(
+                    format!(
+                        "{} must be followed by a {} value",
+                        Keyword::Return.get_code_representation(),
+                        pointer.get_type_symbol().get_pointer_target().unwrap()
+                    ),
+                    0..1,
+                )
+            }
+            (Some(pointer), true) => {
+                let mut typed_pointer =
+                    TypedPointerType::new(pointer.get_type_symbol().get_pointer_target().unwrap());
+                typed_pointer.set_address(pointer.get_address().clone());
+                let destination = q!(typed_pointer.dereference(program_memory, stack_sizes));
+
+                q!(CallLine::evaluate_into_type(
+                    &line[1..],
+                    destination.as_ref(),
+                    program_memory,
+                    reference_stack,
+                    stack_sizes
+                ));
+            }
+        }
+
+        DynamicJumpInstruction::new_alloc(program_memory, return_address.get_address());
+
+        ProcessingResult::Success
+    }
+}
@@ -1,21 +1,89 @@
 use super::LineHandler;
+use crate::address::Address;
 use crate::memory::MemoryManager;
-use crate::processing::arithmetic::evaluate_arithmetic_into_type;
+use crate::processing::arithmetic::{
+    evaluate_arithmetic_into_type, evaluate_arithmetic_to_any_type,
+};
 use crate::processing::blocks::BlockCoordinator;
+use crate::processing::lines::call::CallLine;
 use crate::processing::processor::ProcessingResult;
 
-use crate::processing::symbols::Symbol;
+use crate::processing::symbols::{Operator, Symbol};
 
+use crate::processing::types::typed_pointer::TypedPointerType;
+use crate::processing::types::Type;
 use crate::q;
 
 pub struct VariableAssignmentLine {}
 
+impl VariableAssignmentLine {
+    /// Handles assignment through a pointer e.g. `deref p = 5`
+    fn handle_dereference_assignment(
+        line: &[Symbol],
+        program_memory: &mut MemoryManager,
+        block_coordinator: &mut BlockCoordinator,
+    ) -> Result<(), String> {
+        if line.len() < 4 {
+            return Err("Dereference and Assigner must be followed by a value".to_string());
+        }
+
+        let (stack_sizes, reference_stack) =
+            block_coordinator.get_stack_sizes_and_reference_stack();
+
+        let pointer = evaluate_arithmetic_to_any_type(
+            &line[1..2],
+            program_memory,
+            reference_stack,
+            stack_sizes,
+        )?;
+        let pointer = pointer.as_ref();
+
+        let Some(target) = pointer.get_type_symbol().get_pointer_target() else {
+            return Err(format!(
+                "{} can only be applied to a typed pointer e.g. ptr<int>, received {}",
+                Operator::Dereference,
+                pointer.get_type_symbol()
+            ));
+        };
+
+        let mut typed_pointer = TypedPointerType::new(target);
+        typed_pointer.set_address(pointer.get_address().clone());
+        let destination = typed_pointer.dereference(program_memory, stack_sizes)?;
+
+        let assigner = match &line[2] {
+            Symbol::Assigner(assigner) => assigner,
+            _ => panic!(),
+        };
+
+        evaluate_arithmetic_into_type(
+            &assigner.get_expanded_equivalent(
+                Symbol::BracketedSection(Vec::from(&line[..2])),
+                Vec::from(&line[3..]),
+            ),
+            destination.as_ref(),
+            program_memory,
+            reference_stack,
+            stack_sizes,
+        )
+    }
+}
+
 impl LineHandler for VariableAssignmentLine {
     fn process_line(
         line: &[Symbol],
         program_memory: &mut MemoryManager,
         block_coordinator: &mut BlockCoordinator,
     ) -> ProcessingResult {
+        //? Assignment through a pointer e.g. deref p = 5
+        if let [Symbol::Operator(Operator::Dereference), _, Symbol::Assigner(_), ..] = line {
+            q!(VariableAssignmentLine::handle_dereference_assignment(
+                line,
+                program_memory,
+                block_coordinator
+            ));
+            return ProcessingResult::Success;
+        }
+
         if line.len() < 2
             || !matches!(line[0], Symbol::Name(_))
             || !matches!(line[1], Symbol::Assigner(_))
@@ -24,8 +92,9 @@ impl LineHandler for VariableAssignmentLine {
         }
 
         if line.len() < 3 {
-            return ProcessingResult::Failure(
+            return ProcessingResult::FailureAt(
                 "Name and Assigner must be followed by a value".to_string(),
+                1..2,
             );
         }
 
@@ -37,16 +106,24 @@ impl LineHandler for VariableAssignmentLine {
         let (stack_sizes, reference_stack) =
             block_coordinator.get_stack_sizes_and_reference_stack();
 
-        let variable = q!(q!(reference_stack.get_reference(name)).get_variable_ref());
+        //? Owned so the reference stack can be borrowed mutably for calls e.g. a = f()
+        let variable = q!(q!(reference_stack.get_reference(name)).get_variable_ref()).duplicate();
+
+        if let Address::Immediate(_) = variable.get_address() {
+            return ProcessingResult::FailureAt(
+                format!("Cannot assign to constant '{}'", name.join(".")),
+                0..1,
+            );
+        }
 
         let assigner = match &line[1] {
             Symbol::Assigner(assigner) => assigner,
             _ => panic!(),
         };
 
-        q!(evaluate_arithmetic_into_type(
+        q!(CallLine::evaluate_into_type(
             &assigner.get_expanded_equivalent(line[0].clone(), Vec::from(&line[2..])),
-            variable,
+            variable.as_ref(),
             program_memory,
             reference_stack,
             stack_sizes
@@ -1,17 +1,210 @@
 use super::LineHandler;
+use crate::address::Address;
 use crate::memory::MemoryManager;
-use crate::processing::arithmetic::evaluate_arithmetic_into_type;
+use crate::processing::arithmetic::{
+    evaluate_arithmetic_to_any_type, evaluate_arithmetic_to_types,
+};
+use crate::processing::blocks::function_block::FunctionBlock;
 use crate::processing::blocks::{BlockCoordinator, BlockType, StackSizes};
+use crate::processing::lines::call::CallLine;
 use crate::processing::processor::ProcessingResult;
 use crate::processing::reference_manager::{Reference, ReferenceStack};
 
-use crate::processing::symbols::{Assigner, Symbol};
-use crate::processing::types::TypeFactory;
-use crate::q;
+use crate::processing::symbols::{Assigner, Keyword, Punctuation, Symbol, TypeSymbol};
+use crate::processing::types::enumeration::EnumType;
+use crate::processing::types::tuple::TupleType;
+use crate::processing::types::{Type, TypeFactory};
+use crate::{bx, q};
 
 pub struct VariableInitialisationLine {}
 
 impl VariableInitialisationLine {
+    /// Handles declaring a variable with the type of its value e.g. `let a = 5`
+    fn handle_inferred_initialisation(
+        line: &[Symbol],
+        program_memory: &mut MemoryManager,
+        reference_stack: &mut ReferenceStack,
+        stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        let name = match line {
+            [Symbol::Keyword(Keyword::Let), Symbol::Name(name), Symbol::Assigner(Assigner::Setter), _, ..] => {
+                name
+            }
+            _ => {
+                return Err(format!(
+                    "Inferred variables must be formatted {} [Name] = [Value]",
+                    Keyword::Let.get_code_representation()
+                ))
+            }
+        };
+
+        //? Calls take the function's return type e.g. let t = f()
+        if let [Symbol::Name(function_name), Symbol::List(arguments)] = &line[3..] {
+            let Some(return_type) = FunctionBlock::get_return_type(function_name, reference_stack)
+            else {
+                return Err(format!(
+                    "'{}' doesn't return a value",
+                    function_name.join(".")
+                ));
+            };
+
+            let mut object = TypeFactory::get_unallocated_type(&return_type)?;
+            object.allocate_variable(stack_sizes, program_memory)?;
+            CallLine::handle_call(
+                function_name,
+                arguments,
+                Some(object.as_ref()),
+                program_memory,
+                reference_stack,
+                stack_sizes,
+            )?;
+
+            reference_stack.register_reference(Reference::Variable(object), name.clone())?;
+            return Ok(());
+        }
+
+        let object = {
+            let value = evaluate_arithmetic_to_any_type(
+                &line[3..],
+                program_memory,
+                reference_stack,
+                stack_sizes,
+            )?;
+            let value = value.as_ref();
+
+            //? Copy into a new variable of the same type - duplicating keeps details such as the
+            //? enum definition that a TypeSymbol doesn't carry
+            let mut object = value.duplicate();
+            object.set_address(Address::StackDirect(
+                stack_sizes.increment_stack_size(object.get_length()),
+            ));
+            object
+                .runtime_copy_from(value, program_memory)
+                .map_err(|e| {
+                    format!(
+                        "Failed to initialise '{}' with inferred type {}: {}",
+                        name.join("."),
+                        value.get_type_symbol(),
+                        e
+                    )
+                })?;
+            object
+        };
+
+        reference_stack.register_reference(Reference::Variable(object), name.clone())?;
+
+        Ok(())
+    }
+
+    /// Handles declaring a compile-time constant e.g. `const int a = 5 * 2`. Constants are
+    /// stored as immediates so no memory is used
+    fn handle_constant_initialisation(
+        line: &[Symbol],
+        program_memory: &mut MemoryManager,
+        reference_stack: &mut ReferenceStack,
+        stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        let (type_symbol, name) = match line {
+            [Symbol::Keyword(Keyword::Const), Symbol::Type(type_symbol), Symbol::Name(name), Symbol::Assigner(Assigner::Setter), _, ..] => {
+                (type_symbol, name)
+            }
+            _ => {
+                return Err(format!(
+                    "Constants must be formatted {} [Type] [Name] = [Value]",
+                    Keyword::Const.get_code_representation()
+                ))
+            }
+        };
+
+        let mut object = TypeFactory::get_unallocated_type(type_symbol)?;
+        {
+            let value = evaluate_arithmetic_to_types(
+                &line[4..],
+                &[*type_symbol],
+                program_memory,
+                reference_stack,
+                stack_sizes,
+            )?;
+
+            let Address::Immediate(_) = value.as_ref().get_address() else {
+                return Err(format!(
+                    "The value of constant '{}' must be known at compile time",
+                    name.join(".")
+                ));
+            };
+            object.set_address(value.as_ref().get_address().clone());
+        }
+
+        reference_stack.register_reference(Reference::Variable(object), name.clone())?;
+
+        Ok(())
+    }
+
+    /// Handles declaring several variables from a tuple e.g. `int a, bool b = (5, true)` or
+    /// `int a, bool b = f()`
+    fn handle_destructuring(
+        line: &[Symbol],
+        program_memory: &mut MemoryManager,
+        reference_stack: &mut ReferenceStack,
+        stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        fn format_error() -> String {
+            "Destructuring must be formatted [Type] [Name], [Type] [Name], ... = [Value]"
+                .to_string()
+        }
+
+        let Some(assigner_position) = line
+            .iter()
+            .position(|s| matches!(s, Symbol::Assigner(Assigner::Setter)))
+        else {
+            return Err(format_error());
+        };
+
+        if assigner_position + 1 >= line.len() {
+            return Err(format_error());
+        }
+
+        let mut element_types = Vec::new();
+        let mut names = Vec::new();
+        for declaration in line[..assigner_position]
+            .split(|s| matches!(s, Symbol::Punctuation(Punctuation::ListSeparator)))
+        {
+            let element_type = match declaration {
+                [Symbol::Type(element_type), Symbol::Name(_)] => *element_type,
+                [Symbol::List(list), Symbol::Name(_)] => {
+                    TypeSymbol::from_type_list(list).ok_or_else(format_error)?
+                }
+                _ => return Err(format_error()),
+            };
+            let Symbol::Name(name) = &declaration[1] else {
+                unreachable!()
+            };
+
+            element_types.push(element_type);
+            names.push(name.clone());
+        }
+
+        //? The tuple is the storage for the variables - each refers to its element
+        let tuple_symbol = TypeSymbol::tuple_of(&element_types);
+        let mut tuple = TupleType::new(tuple_symbol.get_tuple_elements().unwrap())?;
+        tuple.allocate_variable(stack_sizes, program_memory)?;
+
+        CallLine::evaluate_into_type(
+            &line[assigner_position + 1..],
+            &tuple,
+            program_memory,
+            reference_stack,
+            stack_sizes,
+        )?;
+
+        for (index, name) in names.into_iter().enumerate() {
+            reference_stack
+                .register_reference(Reference::Variable(tuple.get_element_view(index)?), name)?;
+        }
+
+        Ok(())
+    }
+
     pub fn handle_initialisation(
         line: &[Symbol],
         program_memory: &mut MemoryManager,
@@ -19,6 +212,20 @@ impl VariableInitialisationLine {
         stack_sizes: &mut StackSizes,
         has_value: bool,
     ) -> Result<(), String> {
+        if has_value
+            && line
+                .iter()
+                .take_while(|s| !matches!(s, Symbol::Assigner(_)))
+                .any(|s| matches!(s, Symbol::Punctuation(Punctuation::ListSeparator)))
+        {
+            return VariableInitialisationLine::handle_destructuring(
+                line,
+                program_memory,
+                reference_stack,
+                stack_sizes,
+            );
+        }
+
         if has_value && line.len() < 4 {
             return Err(
                 "Type must be followed by a Name, '=' and value to initialise a variable"
@@ -47,13 +254,26 @@ impl VariableInitialisationLine {
 
         let mut object = match &line[0] {
             Symbol::Type(type_symbol) => TypeFactory::get_unallocated_type(type_symbol)?,
+            //? Tuple e.g. (int, bool) t = (5, true)
+            Symbol::List(list) => match TypeSymbol::from_type_list(list) {
+                Some(tuple_symbol) => TypeFactory::get_unallocated_type(&tuple_symbol)?,
+                None => {
+                    return Err("Tuple types must be a list of types e.g. (int, bool)".to_string())
+                }
+            },
+            Symbol::Name(type_name) => bx!(EnumType::new(
+                reference_stack
+                    .get_reference(type_name)?
+                    .get_enum_ref()?
+                    .clone()
+            )),
             _ => return Err(format!("Type expected, recieved {}", &line[0])),
         };
 
         object.allocate_variable(stack_sizes, program_memory)?;
 
         if has_value {
-            evaluate_arithmetic_into_type(
+            CallLine::evaluate_into_type(
                 &line[3..],
                 object.as_ref(),
                 program_memory,
@@ -74,8 +294,46 @@ impl LineHandler for VariableInitialisationLine {
         program_memory: &mut MemoryManager,
         block_coordinator: &mut BlockCoordinator,
     ) -> ProcessingResult {
-        if line.is_empty() || !matches!(line[0], Symbol::Type(_)) {
-            return ProcessingResult::Unmatched;
+        match line {
+            [Symbol::Type(_), ..] => {}
+            //? Constant e.g. const int a = 5
+            [Symbol::Keyword(Keyword::Const), ..] => {
+                let (reference_stack, stack_sizes) =
+                    block_coordinator.get_reference_stack_and_stack_sizes();
+
+                q!(VariableInitialisationLine::handle_constant_initialisation(
+                    line,
+                    program_memory,
+                    reference_stack,
+                    stack_sizes
+                ));
+                return ProcessingResult::Success;
+            }
+            //? Inferred declaration e.g. let a = 5
+            [Symbol::Keyword(Keyword::Let), ..] => {
+                if matches!(block_coordinator.get_block_handler_type(), BlockType::Class) {
+                    return ProcessingResult::FailureAt(
+                        "Class attributes must be declared with a type".to_string(),
+                        0..1,
+                    );
+                }
+
+                let (reference_stack, stack_sizes) =
+                    block_coordinator.get_reference_stack_and_stack_sizes();
+
+                q!(VariableInitialisationLine::handle_inferred_initialisation(
+                    line,
+                    program_memory,
+                    reference_stack,
+                    stack_sizes
+                ));
+                return ProcessingResult::Success;
+            }
+            //? Enum declaration e.g. shape s = shape.square
+            [Symbol::Name(_), Symbol::Name(_), ..] => {}
+            //? Tuple declaration e.g. (int, bool) t = (5, true)
+            [Symbol::List(_), Symbol::Name(_), ..] => {}
+            _ => return ProcessingResult::Unmatched,
         }
 
         // println!("{}", block_coordinator.get_block_handler_type());
@@ -27,7 +27,12 @@ impl LineHandler for ViewMemoryLine {
         };
 
         if line.len() != 2 {
-            return ProcessingResult::Failure("viewmem must be followed by a variable".to_string());
+            //? Underline what's missing or what shouldn't be there
+            let symbols = if line.len() < 2 { 0..1 } else { 2..line.len() };
+            return ProcessingResult::FailureAt(
+                "viewmem must be followed by a variable".to_string(),
+                symbols,
+            );
         }
 
         let variable = match &line[1] {
@@ -35,8 +40,9 @@ impl LineHandler for ViewMemoryLine {
                 q!(q!(block_coordinator.get_reference(name)).get_variable_ref())
             }
             _ => {
-                return ProcessingResult::Failure(
+                return ProcessingResult::FailureAt(
                     "viewmem must be followed by a variable".to_string(),
+                    1..2,
                 )
             }
         };
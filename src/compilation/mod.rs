@@ -1,4 +1,5 @@
 pub mod blocks;
 pub mod arithmetic;
 mod lines;
+pub mod processor;
 mod types;
\ No newline at end of file
@@ -0,0 +1,479 @@
+use crate::diagnostics::{Diagnostic, DiagnosticCollector, COMPILE_ERROR, INDENTATION_ERROR};
+use crate::lexical_parsing::preprocessor::{InferredType, Line, SymbolData};
+use crate::lints::{LintConfig, Linter};
+use crate::memory::MemoryManager;
+use crate::processing::blocks::{BlockCoordinator, BlockType};
+use crate::processing::lines::base_block::BaseBlockLine;
+use crate::processing::lines::break_continue::BreakContinueLine;
+use crate::processing::lines::call::CallLine;
+use crate::processing::lines::class_line::ClassLine;
+use crate::processing::lines::dump::DumpLine;
+use crate::processing::lines::enum_line::EnumLine;
+use crate::processing::lines::function_line::FunctionLine;
+use crate::processing::lines::if_line::IfLine;
+use crate::processing::lines::match_line::MatchLine;
+use crate::processing::lines::print::PrintLine;
+use crate::processing::lines::return_line::ReturnLine;
+use crate::processing::lines::variable_assignment::VariableAssignmentLine;
+use crate::processing::lines::variable_initialisation::VariableInitialisationLine;
+use crate::processing::lines::view_memory::ViewMemoryLine;
+use crate::processing::lines::while_line::WhileLine;
+use crate::processing::lines::LineHandler;
+use crate::processing::symbols::{Block, Keyword, Symbol, TypeSymbol};
+use std::ops::Range;
+
+pub enum ProcessingResult {
+    Success,
+    Unmatched,
+    Failure(String),
+    /// A failure caused by a range of the line's top level symbols
+    FailureAt(String, Range<usize>),
+}
+
+/// Why a line failed to compile
+pub enum LineError {
+    /// The line is indented more than the block it is in
+    Indentation(String),
+    /// The line couldn't be compiled because of the top level symbols in the range
+    Compile(String, Range<usize>),
+}
+
+/// Compiles the lexed lines into a program.
+///
+/// Lines that fail are reported to `diagnostics` and compilation carries on with the next line
+/// so that every independent error is found. If the failed line opens a block, the block's body
+/// is skipped as it would only report errors caused by the missing block. Stops early once the
+/// collector's error limit is reached.
+///
+/// The types of variables declared with `let` are recorded in `symbol_data.inferred_types`.
+/// Lints are checked as each line compiles, at the levels given by `lint_config`
+pub fn process_symbols(
+    symbol_data: &mut SymbolData,
+    lint_config: &LintConfig,
+    diagnostics: &mut DiagnosticCollector,
+) -> MemoryManager {
+    let mut program_memory = MemoryManager::new();
+    let mut block_coordinator = BlockCoordinator::new(&mut program_memory);
+    let (_, reference_stack) = block_coordinator.get_stack_sizes_and_reference_stack();
+    let mut linter = Linter::new(lint_config, reference_stack.get_depth());
+
+    let inferred_types =
+        process_lines(symbol_data, diagnostics, |line_index, line, diagnostics| {
+            //? Embedded in instructions that can fail at runtime e.g. unwrapping an optional
+            program_memory.set_source_location(symbol_data.get_error_path(line_index));
+            process_line(
+                line_index,
+                symbol_data,
+                &mut program_memory,
+                &mut block_coordinator,
+                &mut linter,
+                diagnostics,
+            )?;
+            Ok(get_inferred_type(line, &mut block_coordinator))
+        });
+
+    if !diagnostics.is_full() {
+        if let Err(e) = block_coordinator.complete(&mut program_memory) {
+            diagnostics.push(Diagnostic::error(e).with_code(COMPILE_ERROR));
+        }
+        linter.finish(symbol_data, diagnostics);
+    }
+    symbol_data.inferred_types = inferred_types;
+
+    program_memory
+}
+
+/// Returns the type given to the variable declared by a `let` line
+fn get_inferred_type(line: &Line, block_coordinator: &mut BlockCoordinator) -> Option<TypeSymbol> {
+    let [Symbol::Keyword(Keyword::Let), Symbol::Name(name), ..] = line.symbols.as_slice() else {
+        return None;
+    };
+
+    let (_, reference_stack) = block_coordinator.get_stack_sizes_and_reference_stack();
+    reference_stack
+        .get_reference(name)
+        .and_then(|reference| reference.get_variable_ref())
+        .ok()
+        .map(|variable| variable.get_type_symbol())
+}
+
+/// Returns whether `symbols` use the variable `name`, including in nested symbols
+fn uses_name(symbols: &[Symbol], name: &[String]) -> bool {
+    symbols.iter().any(|symbol| match symbol {
+        Symbol::Name(used) => used.first() == name.first(),
+        Symbol::BracketedSection(symbols) => uses_name(symbols, name),
+        Symbol::Indexer(indexed, index) => {
+            uses_name(std::slice::from_ref(indexed.as_ref()), name) || uses_name(index, name)
+        }
+        Symbol::List(items) => items.iter().any(|item| uses_name(item, name)),
+        Symbol::MethodCall(value, _, arguments) => {
+            uses_name(std::slice::from_ref(value.as_ref()), name)
+                || arguments.iter().any(|argument| uses_name(argument, name))
+        }
+        _ => false,
+    })
+}
+
+/// Returns whether a variable declared on `declaration` can be used on `line_index` i.e. the block
+/// it was declared in hasn't ended in between
+fn is_in_scope(symbol_data: &SymbolData, declaration: usize, line_index: usize) -> bool {
+    let declared = &symbol_data.lines[declaration];
+    symbol_data.lines[line_index].file_name_index == declared.file_name_index
+        && symbol_data.lines[declaration + 1..=line_index]
+            .iter()
+            .filter(|line| {
+                line.file_name_index == declared.file_name_index && !line.symbols.is_empty()
+            })
+            .all(|line| line.indentation >= declared.indentation)
+}
+
+/// Runs `compile_line` on every line, reporting failures and skipping the bodies of blocks whose
+/// opening line failed (see `process_symbols`). `compile_line` can report warnings to the
+/// collector it is given and returns the type of the variable declared by the line if it was
+/// inferred.
+///
+/// Returns the inferred types. Errors on lines using a variable with an inferred type point at
+/// its declaration as the type may not be the one expected
+fn process_lines(
+    symbol_data: &SymbolData,
+    diagnostics: &mut DiagnosticCollector,
+    mut compile_line: impl FnMut(
+        usize,
+        &Line,
+        &mut DiagnosticCollector,
+    ) -> Result<Option<TypeSymbol>, LineError>,
+) -> Vec<InferredType> {
+    //? Lines indented more than this are in the body of a block that failed to open
+    let mut skipped_block: Option<usize> = None;
+    let mut inferred_types: Vec<InferredType> = Vec::new();
+
+    for (line_index, line) in symbol_data.lines.iter().enumerate() {
+        if diagnostics.is_full() {
+            break;
+        }
+        if line.symbols.is_empty() {
+            continue;
+        }
+
+        if let Some(indentation) = skipped_block {
+            //? elif and else belong to the skipped block
+            let continues_block = matches!(
+                line.symbols.first(),
+                Some(Symbol::Block(Block::Elif | Block::Else))
+            );
+            if line.indentation > indentation
+                || (line.indentation == indentation && continues_block)
+            {
+                continue;
+            }
+            skipped_block = None;
+        }
+
+        let (message, code, symbols) = match compile_line(line_index, line, diagnostics) {
+            Ok(inferred_type) => {
+                if let (Some(type_symbol), Some(Symbol::Name(name))) =
+                    (inferred_type, line.symbols.get(1))
+                {
+                    inferred_types.push(InferredType {
+                        line_index,
+                        name: name.clone(),
+                        type_symbol,
+                    });
+                }
+                continue;
+            }
+            Err(LineError::Indentation(message)) => (message, INDENTATION_ERROR, 0..1),
+            Err(LineError::Compile(message, symbols)) => (message, COMPILE_ERROR, symbols),
+        };
+
+        let mut diagnostic = Diagnostic::error(message)
+            .with_code(code)
+            .with_primary(symbol_data.get_range_label(line_index, symbols, String::new()));
+        //? The latest declaration of each name is the one in use
+        let mut noted: Vec<&[String]> = Vec::new();
+        for inferred in inferred_types.iter().rev() {
+            if noted.contains(&inferred.name.as_slice())
+                || !uses_name(&line.symbols, &inferred.name)
+                || !is_in_scope(symbol_data, inferred.line_index, line_index)
+            {
+                continue;
+            }
+            noted.push(&inferred.name);
+            diagnostic = diagnostic.with_secondary(symbol_data.get_label(
+                inferred.line_index,
+                &[1],
+                format!(
+                    "'{}' has the inferred type {}",
+                    inferred.name.join("."),
+                    inferred.type_symbol
+                ),
+            ));
+        }
+        diagnostics.push(diagnostic);
+
+        if matches!(line.symbols.first(), Some(Symbol::Block(_))) {
+            skipped_block = Some(line.indentation);
+        } else if code == INDENTATION_ERROR {
+            //? Lines following an unexpected indent are usually indented the same way
+            skipped_block = Some(line.indentation - 1);
+        }
+    }
+
+    inferred_types
+}
+
+/// Compiles a line, first exiting any blocks that the line is outside of. The line is checked by
+/// `linter` once the blocks have been exited so that it sees the scopes the line is compiled in
+fn process_line(
+    line_index: usize,
+    symbol_data: &SymbolData,
+    program_memory: &mut MemoryManager,
+    block_coordinator: &mut BlockCoordinator,
+    linter: &mut Linter,
+    diagnostics: &mut DiagnosticCollector,
+) -> Result<(), LineError> {
+    let line = &symbol_data.lines[line_index];
+    let symbols = line.symbols.as_slice();
+    //? Errors that can't be traced to a symbol underline the whole statement
+    let statement = 0..symbols.len();
+
+    if line.indentation > block_coordinator.get_indentation() {
+        return Err(LineError::Indentation(format!(
+            "Unexpected indentation - expected {} levels but found {}",
+            block_coordinator.get_indentation(),
+            line.indentation
+        )));
+    }
+
+    while line.indentation < block_coordinator.get_indentation() {
+        //? Lines such as elif continue the block rather than ending it
+        let exited = block_coordinator
+            .exit_block_handler(program_memory, symbols)
+            .map_err(|e| LineError::Compile(e, statement.clone()))?;
+        if !exited {
+            let (_, reference_stack) = block_coordinator.get_stack_sizes_and_reference_stack();
+            linter.continue_block(line_index, reference_stack, symbol_data, diagnostics);
+            return Ok(());
+        }
+    }
+
+    let block_type = block_coordinator.get_block_handler_type();
+    let (_, reference_stack) = block_coordinator.get_stack_sizes_and_reference_stack();
+    linter.before_line(
+        line_index,
+        &block_type,
+        reference_stack,
+        symbol_data,
+        diagnostics,
+    );
+
+    //? Loops and functions know when a line follows a break, continue or return
+    if matches!(block_type, BlockType::While | BlockType::Function) {
+        if let Err(reason) = block_coordinator
+            .get_block_handler_mut()
+            .handle_line(symbols)
+        {
+            linter.report_unreachable(line_index, reason, symbol_data, diagnostics);
+        }
+    }
+
+    process_line_handlers(symbols, program_memory, block_coordinator)?;
+
+    let (_, reference_stack) = block_coordinator.get_stack_sizes_and_reference_stack();
+    linter.after_line(line_index, &block_type, reference_stack, symbol_data);
+    Ok(())
+}
+
+/// Compiles a line with the first line handler that matches it
+fn process_line_handlers(
+    symbols: &[Symbol],
+    program_memory: &mut MemoryManager,
+    block_coordinator: &mut BlockCoordinator,
+) -> Result<(), LineError> {
+    let statement = 0..symbols.len();
+
+    macro_rules! process_line {
+        ($line_handler: ident) => {
+            match $line_handler::process_line(symbols, program_memory, block_coordinator) {
+                ProcessingResult::Success => return Ok(()),
+                ProcessingResult::Failure(e) => return Err(LineError::Compile(e, statement)),
+                ProcessingResult::FailureAt(e, symbols) => {
+                    return Err(LineError::Compile(e, symbols))
+                }
+                ProcessingResult::Unmatched => {}
+            }
+        };
+    }
+
+    process_line!(VariableInitialisationLine);
+    process_line!(VariableAssignmentLine);
+    process_line!(CallLine);
+    process_line!(PrintLine);
+    process_line!(IfLine);
+    process_line!(WhileLine);
+    process_line!(MatchLine);
+    process_line!(BaseBlockLine);
+    process_line!(FunctionLine);
+    process_line!(ClassLine);
+    process_line!(EnumLine);
+    process_line!(BreakContinueLine);
+    process_line!(ReturnLine);
+    process_line!(DumpLine);
+    process_line!(ViewMemoryLine);
+
+    Err(LineError::Compile(
+        "Line not recognised".to_string(),
+        statement,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::Label;
+    use crate::lexical_parsing::preprocessor::convert_to_symbols;
+    use crate::lexical_parsing::span::Span;
+
+    const SOURCE: &str = "int a = bad
+if bad
+    int b = bad
+elif a
+    int c = bad
+int d = 1
+while a
+    int e = bad
+print bad
+";
+
+    /// Compiles `SOURCE`, failing any line that uses the name `bad` at that symbol. Returns the
+    /// (1-based) line numbers compiled and the labels of the errors reported
+    fn compile_labels(name: &str, error_limit: usize) -> (Vec<usize>, Vec<Label>) {
+        let path = std::env::temp_dir().join(format!("whython_processor_{}.why", name));
+        std::fs::write(&path, SOURCE).unwrap();
+        let mut symbol_data = SymbolData::new();
+        let mut diagnostics = DiagnosticCollector::new(error_limit);
+        convert_to_symbols(
+            path.to_string_lossy().to_string(),
+            &mut symbol_data,
+            &mut diagnostics,
+        )
+        .unwrap();
+
+        let bad = Symbol::Name(vec!["bad".to_string()]);
+        let mut compiled = Vec::new();
+        process_lines(&symbol_data, &mut diagnostics, |_, line, _| {
+            compiled.push(line.line_index + 1);
+            match line.symbols.iter().position(|s| *s == bad) {
+                Some(i) => Err(LineError::Compile("Uses bad".to_string(), i..i + 1)),
+                None => Ok(None),
+            }
+        });
+
+        let reported = diagnostics
+            .get_diagnostics()
+            .iter()
+            .map(|d| d.primary.clone().unwrap())
+            .collect();
+        (compiled, reported)
+    }
+
+    /// Like `compile_labels` but only returns the (1-based) line numbers reported
+    fn compile(name: &str, error_limit: usize) -> (Vec<usize>, Vec<usize>) {
+        let (compiled, reported) = compile_labels(name, error_limit);
+        let reported = reported.iter().map(|l| l.line_index + 1).collect();
+        (compiled, reported)
+    }
+
+    #[test]
+    fn skips_failed_blocks() {
+        let (compiled, reported) = compile("skips_failed_blocks", 50);
+        assert_eq!(compiled, vec![1, 2, 6, 7, 8, 9]);
+        assert_eq!(reported, vec![1, 2, 8, 9]);
+    }
+
+    #[test]
+    fn stops_at_error_limit() {
+        let (compiled, reported) = compile("stops_at_error_limit", 2);
+        assert_eq!(compiled, vec![1, 2]);
+        assert_eq!(reported, vec![1, 2]);
+    }
+
+    #[test]
+    fn underlines_failed_symbols() {
+        let (_, reported) = compile_labels("underlines_failed_symbols", 50);
+        let spans: Vec<Option<Span>> = reported.iter().map(|l| l.span).collect();
+        assert_eq!(
+            spans,
+            vec![
+                Some(Span::new(8, 11)),
+                Some(Span::new(3, 6)),
+                Some(Span::new(12, 15)),
+                Some(Span::new(6, 9)),
+            ]
+        );
+    }
+
+    #[test]
+    fn notes_inferred_types() {
+        let source = "let a = 5
+let b = a
+if b
+    let a = 1
+    bool c = a
+bool d = a
+bool e = b
+";
+        let path = std::env::temp_dir().join("whython_processor_notes_inferred_types.why");
+        std::fs::write(&path, source).unwrap();
+        let mut symbol_data = SymbolData::new();
+        let mut diagnostics = DiagnosticCollector::new(50);
+        convert_to_symbols(
+            path.to_string_lossy().to_string(),
+            &mut symbol_data,
+            &mut diagnostics,
+        )
+        .unwrap();
+
+        //? Every let is an int and every bool declaration fails
+        let inferred_types = process_lines(&symbol_data, &mut diagnostics, |_, line, _| {
+            match line.symbols.first() {
+                Some(Symbol::Keyword(Keyword::Let)) => Ok(Some(TypeSymbol::Integer)),
+                Some(Symbol::Type(TypeSymbol::Boolean)) => {
+                    Err(LineError::Compile("Expected bool".to_string(), 3..4))
+                }
+                _ => Ok(None),
+            }
+        });
+        symbol_data.inferred_types = inferred_types;
+
+        assert_eq!(
+            symbol_data
+                .inferred_types
+                .iter()
+                .map(|t| t.line_index + 1)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 4]
+        );
+        assert_eq!(symbol_data.inferred_types[2].name, vec!["a".to_string()]);
+        assert_eq!(
+            symbol_data.inferred_types[2].type_symbol,
+            TypeSymbol::Integer
+        );
+
+        //? Each error points at the declaration of the variable in scope
+        let notes: Vec<Vec<(usize, String)>> = diagnostics
+            .get_diagnostics()
+            .iter()
+            .map(|d| {
+                d.secondary
+                    .iter()
+                    .map(|l| (l.line_index + 1, l.message.clone()))
+                    .collect()
+            })
+            .collect();
+        let note = |line: usize, name: &str| {
+            vec![(line, format!("'{}' has the inferred type int", name))]
+        };
+        assert_eq!(notes, vec![note(4, "a"), note(1, "a"), note(2, "b")]);
+    }
+}
@@ -8,10 +8,23 @@ use crate::bx;
 use crate::memory::MemoryManager;
 use crate::processing::blocks::StackSizes;
 use crate::processing::instructions::copy_3::CopyInstruction;
+use crate::processing::types::character::CharWrapper;
+use crate::processing::types::float::FloatWrapper;
+use crate::processing::types::integer::IntegerWrapper;
+use crate::processing::types::optional::OptionalType;
 use crate::processing::types::pointer::PointerWrapper;
+use crate::processing::types::tuple::TupleType;
+use crate::processing::types::typed_pointer::TypedPointerType;
 
 pub mod boolean;
+pub mod character;
+pub mod enumeration;
+pub mod float;
+pub mod integer;
+pub mod optional;
 pub mod pointer;
+pub mod tuple;
+pub mod typed_pointer;
 
 pub trait UninstantiatedType {
     fn instantiate(&self) -> Box<dyn Type>;
@@ -73,12 +86,31 @@ pub trait Type {
         stack_sizes: &mut StackSizes,
     ) -> Result<(), String>;
 
+    /// Evaluates the operation at compile time if both operands are constants. Returns `None` if
+    /// the operation can't be folded
+    fn fold_operation(
+        &self,
+        _operator: &Operator,
+        _rhs: &dyn Type,
+    ) -> Result<Option<Address>, String> {
+        Ok(None)
+    }
+
+    /// Evaluates the prefix operation at compile time if the operand is a constant. Returns
+    /// `None` if the operation can't be folded
+    fn fold_prefix_operation(&self, _operator: &Operator) -> Result<Option<Address>, String> {
+        Ok(None)
+    }
+
     fn get_address(&self) -> &Address;
 
     fn get_length(&self) -> usize;
 
     fn get_address_mut(&mut self) -> &mut Address;
 
+    /// Points the type at an existing region of memory instead of allocating a new one
+    fn set_address(&mut self, address: Address);
+
     fn run_method(
         &self,
         method_name: &String,
@@ -133,19 +165,45 @@ pub struct TypeFactory {
 impl TypeFactory {
     pub fn get() -> Self {
         Self {
-            uninstantiated_types: vec![bx!(BoolWrapper {}), bx!(PointerWrapper {})],
+            uninstantiated_types: vec![
+                bx!(BoolWrapper {}),
+                bx!(PointerWrapper {}),
+                bx!(IntegerWrapper::new(TypeSymbol::Integer)),
+                bx!(IntegerWrapper::new(TypeSymbol::I8)),
+                bx!(IntegerWrapper::new(TypeSymbol::I16)),
+                bx!(IntegerWrapper::new(TypeSymbol::I32)),
+                bx!(IntegerWrapper::new(TypeSymbol::I64)),
+                bx!(IntegerWrapper::new(TypeSymbol::U8)),
+                bx!(IntegerWrapper::new(TypeSymbol::U16)),
+                bx!(IntegerWrapper::new(TypeSymbol::U32)),
+                bx!(IntegerWrapper::new(TypeSymbol::U64)),
+                bx!(FloatWrapper {}),
+                bx!(CharWrapper {}),
+            ],
         }
     }
 
     pub fn get_unallocated_type(new_type: &TypeSymbol) -> Result<Box<dyn Type>, String> {
+        if let Some(target) = new_type.get_pointer_target() {
+            return Ok(bx!(TypedPointerType::new(target)));
+        }
+
+        if let Some(inner) = new_type.get_optional_inner() {
+            return Ok(bx!(OptionalType::new(inner)?));
+        }
+
+        if let Some(elements) = new_type.get_tuple_elements() {
+            return Ok(bx!(TupleType::new(elements)?));
+        }
+
         let factory = Self::get();
         let Some(wrapper) = factory
             .uninstantiated_types
             .iter()
             .find(|t| t.get_type_symbol() == *new_type)
-            else {
-                return Err(format!("Type {:?} cannot be instantiated", new_type));
-            };
+        else {
+            return Err(format!("Type {:?} cannot be instantiated", new_type));
+        };
 
         return Ok(wrapper.instantiate());
     }
@@ -155,10 +213,20 @@ impl TypeFactory {
         prefered_type: Option<&TypeSymbol>,
     ) -> Result<TypeSymbol, String> {
         match literal {
+            Literal::None if matches!(prefered_type, Some(TypeSymbol::Optional(_))) => {
+                Ok(*prefered_type.unwrap())
+            }
             Literal::Bool(_) => Ok(TypeSymbol::Boolean),
+            Literal::Float(_) => Ok(TypeSymbol::Float),
+            Literal::Char(_) => Ok(TypeSymbol::Character),
+            Literal::TypedInt(_, t) => Ok(*t),
             Literal::Int(_) => Ok(match prefered_type {
                 None => TypeSymbol::Integer,
                 Some(TypeSymbol::Pointer) => TypeSymbol::Pointer,
+                //? Offsets for typed pointers are counted in elements
+                Some(TypeSymbol::TypedPointer(_)) => TypeSymbol::Pointer,
+                Some(t) if t.get_integer_format().is_some() => *t,
+                Some(TypeSymbol::Float) => TypeSymbol::Float,
                 _ => TypeSymbol::Integer,
             }),
             _ => Err(format!(
@@ -168,6 +236,19 @@ impl TypeFactory {
         }
     }
 
+    /// Returns the default type for a literal referring to the literal's value as an immediate
+    /// rather than allocating a variable
+    pub fn get_constant_type_for_literal(
+        literal: &Literal,
+        prefered_type: Option<&TypeSymbol>,
+    ) -> Result<Box<dyn Type>, String> {
+        let type_symbol = Self::get_default_type_for_literal(literal, prefered_type)?;
+        let mut t = Self::get_unallocated_type(&type_symbol)?;
+        let constant = t.get_constant(literal)?;
+        t.set_address(constant);
+        Ok(t)
+    }
+
     pub fn get_default_instantiated_type_for_literal(
         literal: &Literal,
         stack: &mut StackSizes,
@@ -187,3 +268,391 @@ impl Default for TypeFactory {
         Self::get()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every built-in type operators are checked between
+    const TYPES: [TypeSymbol; 13] = [
+        TypeSymbol::Boolean,
+        TypeSymbol::Pointer,
+        TypeSymbol::Integer,
+        TypeSymbol::I8,
+        TypeSymbol::I16,
+        TypeSymbol::I32,
+        TypeSymbol::I64,
+        TypeSymbol::U8,
+        TypeSymbol::U16,
+        TypeSymbol::U32,
+        TypeSymbol::U64,
+        TypeSymbol::Float,
+        TypeSymbol::Character,
+    ];
+
+    /// Operators handled by `Type::operate`. Casts, references and unwrapping are handled by
+    /// `arithmetic` before reaching the type
+    const OPERATORS: [Operator; 17] = [
+        Operator::Add,
+        Operator::Subtract,
+        Operator::Product,
+        Operator::Divide,
+        Operator::Modulo,
+        Operator::Greater,
+        Operator::Less,
+        Operator::GreaterEqual,
+        Operator::LessEqual,
+        Operator::Equal,
+        Operator::NotEqual,
+        Operator::Or,
+        Operator::And,
+        Operator::Xor,
+        Operator::LeftShift,
+        Operator::RightShift,
+        Operator::Not,
+    ];
+
+    /// Types whose operations are always evaluated at runtime, even with constant operands
+    const UNFOLDED_TYPES: [TypeSymbol; 1] = [TypeSymbol::Pointer];
+
+    const PREFIX_OPERATORS: [Operator; 3] = [Operator::Negate, Operator::Not, Operator::BitwiseNot];
+
+    /// The type of the left operand, the operator, the operands and the expected result, or
+    /// `None` if the operation isn't supported. The operands take the type if they can (see
+    /// `TypeFactory::get_default_type_for_literal`)
+    type Case = (TypeSymbol, Operator, Vec<Literal>, Option<Literal>);
+
+    fn get_cases() -> Vec<Case> {
+        use Literal::{Bool, Char, Float, Int};
+        use Operator::*;
+        use TypeSymbol::{Boolean, Character, Integer, Pointer, I8, U8};
+        let float = TypeSymbol::Float;
+
+        vec![
+            (Integer, Add, vec![Int(2), Int(3)], Some(Int(5))),
+            (Integer, Subtract, vec![Int(2), Int(3)], Some(Int(-1))),
+            (Integer, Product, vec![Int(4), Int(5)], Some(Int(20))),
+            (Integer, Divide, vec![Int(7), Int(2)], Some(Int(3))),
+            (Integer, Modulo, vec![Int(7), Int(2)], Some(Int(1))),
+            (Integer, And, vec![Int(6), Int(3)], Some(Int(2))),
+            (Integer, Or, vec![Int(6), Int(3)], Some(Int(7))),
+            (Integer, Xor, vec![Int(6), Int(3)], Some(Int(5))),
+            (Integer, LeftShift, vec![Int(1), Int(4)], Some(Int(16))),
+            (Integer, RightShift, vec![Int(16), Int(4)], Some(Int(1))),
+            (Integer, Greater, vec![Int(2), Int(3)], Some(Bool(false))),
+            (Integer, Less, vec![Int(2), Int(3)], Some(Bool(true))),
+            (
+                Integer,
+                GreaterEqual,
+                vec![Int(3), Int(3)],
+                Some(Bool(true)),
+            ),
+            (Integer, LessEqual, vec![Int(4), Int(3)], Some(Bool(false))),
+            (Integer, Equal, vec![Int(3), Int(3)], Some(Bool(true))),
+            (Integer, NotEqual, vec![Int(3), Int(3)], Some(Bool(false))),
+            (Integer, Negate, vec![Int(5)], Some(Int(-5))),
+            (Integer, BitwiseNot, vec![Int(0)], Some(Int(-1))),
+            (Integer, Not, vec![Int(0)], None),
+            (Integer, Add, vec![Int(1), Float(1.0)], None),
+            (Integer, Add, vec![Int(1), Literal::TypedInt(1, U8)], None),
+            (U8, Add, vec![Int(200), Int(55)], Some(Int(255))),
+            (U8, BitwiseNot, vec![Int(0)], Some(Int(255))),
+            (U8, Negate, vec![Int(1)], None),
+            (I8, Negate, vec![Int(-127)], Some(Int(127))),
+            (
+                Boolean,
+                And,
+                vec![Bool(true), Bool(false)],
+                Some(Bool(false)),
+            ),
+            (Boolean, Or, vec![Bool(true), Bool(false)], Some(Bool(true))),
+            (
+                Boolean,
+                Xor,
+                vec![Bool(true), Bool(true)],
+                Some(Bool(false)),
+            ),
+            (
+                Boolean,
+                Equal,
+                vec![Bool(true), Bool(true)],
+                Some(Bool(true)),
+            ),
+            (
+                Boolean,
+                NotEqual,
+                vec![Bool(true), Bool(true)],
+                Some(Bool(false)),
+            ),
+            (Boolean, Not, vec![Bool(true)], Some(Bool(false))),
+            (Boolean, Add, vec![Bool(true), Bool(true)], None),
+            (Boolean, Less, vec![Bool(true), Bool(false)], None),
+            (Boolean, Equal, vec![Bool(true), Int(1)], None),
+            (
+                Character,
+                Equal,
+                vec![Char('a'), Char('a')],
+                Some(Bool(true)),
+            ),
+            (
+                Character,
+                NotEqual,
+                vec![Char('a'), Char('b')],
+                Some(Bool(true)),
+            ),
+            (
+                Character,
+                Less,
+                vec![Char('a'), Char('b')],
+                Some(Bool(true)),
+            ),
+            (
+                Character,
+                GreaterEqual,
+                vec![Char('a'), Char('b')],
+                Some(Bool(false)),
+            ),
+            (Character, Add, vec![Char('a'), Char('b')], None),
+            (Character, Negate, vec![Char('a')], None),
+            (float, Add, vec![Float(1.5), Float(2.25)], Some(Float(3.75))),
+            (
+                float,
+                Subtract,
+                vec![Float(1.5), Float(2.25)],
+                Some(Float(-0.75)),
+            ),
+            (
+                float,
+                Product,
+                vec![Float(1.5), Float(2.0)],
+                Some(Float(3.0)),
+            ),
+            (
+                float,
+                Divide,
+                vec![Float(1.0), Float(4.0)],
+                Some(Float(0.25)),
+            ),
+            (float, Less, vec![Float(1.0), Float(4.0)], Some(Bool(true))),
+            (float, Equal, vec![Float(1.0), Float(1.0)], Some(Bool(true))),
+            (float, Negate, vec![Float(1.5)], Some(Float(-1.5))),
+            (float, Xor, vec![Float(1.0), Float(1.0)], None),
+            (float, BitwiseNot, vec![Float(1.0)], None),
+            (Pointer, Equal, vec![Int(8), Int(8)], Some(Bool(true))),
+            (Pointer, Less, vec![Int(8), Int(16)], Some(Bool(true))),
+            (Pointer, Add, vec![Int(8), Int(16)], Some(Int(24))),
+            (Pointer, Xor, vec![Int(8), Int(16)], None),
+        ]
+    }
+
+    /// Gets the bytes of a constant, as used by folded operations
+    fn get_constant_bytes(literal: &Literal, type_symbol: &TypeSymbol) -> Vec<u8> {
+        match TypeFactory::get_constant_type_for_literal(literal, Some(type_symbol))
+            .unwrap()
+            .get_address()
+        {
+            Address::Immediate(data) => data.clone(),
+            _ => panic!("Constant {} is not an immediate", literal),
+        }
+    }
+
+    /// Gets a variable of `type_symbol` so that operations on it can't be folded
+    fn get_variable(
+        type_symbol: &TypeSymbol,
+        stack_sizes: &mut StackSizes,
+        program_memory: &mut MemoryManager,
+    ) -> Box<dyn Type> {
+        let mut variable = TypeFactory::get_unallocated_type(type_symbol).unwrap();
+        variable
+            .allocate_variable(stack_sizes, program_memory)
+            .unwrap();
+        variable
+    }
+
+    fn assert_not_supported(result: Result<(), String>, case: &str) {
+        match result {
+            Err(e) if e.contains("not supported") => {}
+            other => panic!(
+                "{}: expected a 'not supported' error, got {:?}",
+                case, other
+            ),
+        }
+    }
+
+    #[test]
+    fn operator_table_folded() {
+        for (type_symbol, operator, operands, expected) in get_cases() {
+            let case = format!("{} {} {:?}", type_symbol, operator, operands);
+            let lhs = TypeFactory::get_constant_type_for_literal(&operands[0], Some(&type_symbol))
+                .unwrap();
+
+            let (result_types, folded) = match operands.get(1) {
+                Some(rhs) => {
+                    let rhs = TypeFactory::get_constant_type_for_literal(rhs, Some(&type_symbol))
+                        .unwrap();
+                    (
+                        lhs.get_operation_result_type(&operator, &rhs.get_type_symbol()),
+                        lhs.fold_operation(&operator, rhs.as_ref()),
+                    )
+                }
+                None => (
+                    lhs.get_prefix_operation_result_type(&operator),
+                    lhs.fold_prefix_operation(&operator),
+                ),
+            };
+
+            match expected {
+                Some(_) if UNFOLDED_TYPES.contains(&type_symbol) => {
+                    assert!(!result_types.is_empty(), "{}", case);
+                    assert!(matches!(folded, Ok(None)), "{}", case);
+                }
+                Some(expected) => {
+                    let Ok(Some(Address::Immediate(data))) = folded else {
+                        panic!("{}: expected a folded constant", case);
+                    };
+                    assert_eq!(
+                        data,
+                        get_constant_bytes(&expected, &result_types[0]),
+                        "{}",
+                        case
+                    );
+                }
+                None => {
+                    assert!(result_types.is_empty(), "{}", case);
+                    assert!(matches!(folded, Ok(None)), "{}", case);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn operator_table_runtime() {
+        let mut program_memory = MemoryManager::new();
+        let mut stack_sizes = StackSizes::new();
+
+        for (type_symbol, operator, operands, expected) in get_cases() {
+            let case = format!("{} {} {:?}", type_symbol, operator, operands);
+            let lhs = get_variable(&type_symbol, &mut stack_sizes, &mut program_memory);
+
+            let (result_types, rhs) = match operands.get(1) {
+                Some(rhs) => {
+                    let rhs_type =
+                        TypeFactory::get_default_type_for_literal(rhs, Some(&type_symbol)).unwrap();
+                    let rhs = get_variable(&rhs_type, &mut stack_sizes, &mut program_memory);
+                    (
+                        lhs.get_operation_result_type(&operator, &rhs_type),
+                        Some(rhs),
+                    )
+                }
+                None => (lhs.get_prefix_operation_result_type(&operator), None),
+            };
+
+            let Some(expected) = expected else {
+                assert!(result_types.is_empty(), "{}", case);
+                let destination = get_variable(&type_symbol, &mut stack_sizes, &mut program_memory);
+                let result = match &rhs {
+                    Some(rhs) => lhs.operate(
+                        &operator,
+                        rhs.as_ref(),
+                        destination.as_ref(),
+                        &mut program_memory,
+                        &mut stack_sizes,
+                    ),
+                    None => lhs.operate_prefix(
+                        &operator,
+                        destination.as_ref(),
+                        &mut program_memory,
+                        &mut stack_sizes,
+                    ),
+                };
+                assert_not_supported(result, &case);
+                continue;
+            };
+
+            let expected_type =
+                TypeFactory::get_default_type_for_literal(&expected, Some(&result_types[0]))
+                    .unwrap();
+            assert_eq!(result_types[0], expected_type, "{}", case);
+
+            let destination = get_variable(&result_types[0], &mut stack_sizes, &mut program_memory);
+            let start = program_memory.position();
+            let result = match &rhs {
+                Some(rhs) => lhs.operate(
+                    &operator,
+                    rhs.as_ref(),
+                    destination.as_ref(),
+                    &mut program_memory,
+                    &mut stack_sizes,
+                ),
+                None => lhs.operate_prefix(
+                    &operator,
+                    destination.as_ref(),
+                    &mut program_memory,
+                    &mut stack_sizes,
+                ),
+            };
+            assert_eq!(result, Ok(()), "{}", case);
+            assert!(
+                program_memory.position() > start,
+                "{}: no instructions were written",
+                case
+            );
+        }
+    }
+
+    /// Every operator between every pair of types is either supported or fails with the standard
+    /// error without writing any instructions
+    #[test]
+    fn operator_matrix() {
+        let mut program_memory = MemoryManager::new();
+        let mut stack_sizes = StackSizes::new();
+
+        for lhs_type in TYPES.iter() {
+            let lhs = get_variable(lhs_type, &mut stack_sizes, &mut program_memory);
+
+            for operator in PREFIX_OPERATORS.iter() {
+                let case = format!("{} {}", operator, lhs_type);
+                if lhs.get_prefix_operation_result_type(operator).is_empty() {
+                    let start = program_memory.position();
+                    let destination = get_variable(lhs_type, &mut stack_sizes, &mut program_memory);
+                    assert_not_supported(
+                        lhs.operate_prefix(
+                            operator,
+                            destination.as_ref(),
+                            &mut program_memory,
+                            &mut stack_sizes,
+                        ),
+                        &case,
+                    );
+                    assert_eq!(program_memory.position(), start, "{}", case);
+                }
+            }
+
+            for rhs_type in TYPES.iter() {
+                let rhs = get_variable(rhs_type, &mut stack_sizes, &mut program_memory);
+                for operator in OPERATORS.iter() {
+                    let case = format!("{} {} {}", lhs_type, operator, rhs_type);
+                    let result_types = lhs.get_operation_result_type(operator, rhs_type);
+                    let destination_type = result_types.first().unwrap_or(lhs_type);
+                    let destination =
+                        get_variable(destination_type, &mut stack_sizes, &mut program_memory);
+
+                    let start = program_memory.position();
+                    let result = lhs.operate(
+                        operator,
+                        rhs.as_ref(),
+                        destination.as_ref(),
+                        &mut program_memory,
+                        &mut stack_sizes,
+                    );
+                    if result_types.is_empty() {
+                        assert_not_supported(result, &case);
+                        assert_eq!(program_memory.position(), start, "{}", case);
+                    } else {
+                        assert_eq!(result, Ok(()), "{}", case);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -6,6 +6,8 @@ use crate::processing::instructions::binary_and_8::BinaryAndInstruction;
 use crate::processing::instructions::binary_not_7::BinaryNotInstruction;
 use crate::processing::instructions::binary_or_12::BinaryOrInstruction;
 use crate::processing::instructions::copy_3::CopyInstruction;
+use crate::processing::instructions::equality_14::EqualityInstruction;
+use crate::processing::instructions::not_equal_15::NotEqualInstruction;
 use crate::processing::symbols::Literal;
 use crate::processing::types::PrefixOperation;
 use crate::util::warn;
@@ -19,7 +21,11 @@ use super::{Operation, Type};
 
 default_type_wrapper_struct_and_impl!(BoolWrapper, BoolType, TypeSymbol::Boolean);
 default_type_struct!(BoolType);
-default_type_initialiser!(BoolType, (BoolAnd, BoolOr), (BoolNot));
+default_type_initialiser!(
+    BoolType,
+    (BoolAnd, BoolOr, BoolXor, BoolEqual, BoolNotEqual),
+    (BoolNot)
+);
 
 pub const BOOL_TRUE: u8 = 0xFF;
 pub const BOOL_FALSE: u8 = 0x00;
@@ -107,6 +113,49 @@ impl Type for BoolType {
 
     default_type_operate_impl!(BoolType);
 
+    fn fold_operation(
+        &self,
+        operator: &Operator,
+        rhs: &dyn Type,
+    ) -> Result<Option<Address>, String> {
+        let (Address::Immediate(lhs_data), Address::Immediate(rhs_data)) =
+            (self.get_address(), rhs.get_address())
+        else {
+            return Ok(None);
+        };
+        if rhs.get_type_symbol() != TypeSymbol::Boolean {
+            return Ok(None);
+        }
+
+        let (lhs, rhs) = (lhs_data[0] != BOOL_FALSE, rhs_data[0] != BOOL_FALSE);
+        let value = match operator {
+            Operator::And => lhs && rhs,
+            Operator::Or => lhs || rhs,
+            Operator::Xor | Operator::NotEqual => lhs != rhs,
+            Operator::Equal => lhs == rhs,
+            _ => return Ok(None),
+        };
+
+        Ok(Some(Address::Immediate(vec![if value {
+            BOOL_TRUE
+        } else {
+            BOOL_FALSE
+        }])))
+    }
+
+    fn fold_prefix_operation(&self, operator: &Operator) -> Result<Option<Address>, String> {
+        match (operator, self.get_address()) {
+            (Operator::Not, Address::Immediate(data)) => {
+                Ok(Some(Address::Immediate(vec![if data[0] == BOOL_FALSE {
+                    BOOL_TRUE
+                } else {
+                    BOOL_FALSE
+                }])))
+            }
+            _ => Ok(None),
+        }
+    }
+
     fn get_address(&self) -> &Address {
         self.address.as_ref().unwrap()
     }
@@ -119,6 +168,10 @@ impl Type for BoolType {
         self.address.as_mut().unwrap()
     }
 
+    fn set_address(&mut self, address: Address) {
+        self.address = Some(address);
+    }
+
     fn duplicate(&self) -> Box<dyn Type> {
         let mut t = BoolType::new();
         t.address = self.address.as_ref().cloned();
@@ -202,11 +255,123 @@ impl Operation<BoolType> for BoolOr {
     }
 }
 
+pub struct BoolXor {}
+
+impl Operation<BoolType> for BoolXor {
+    fn get_symbol(&self) -> Operator {
+        Operator::Xor
+    }
+
+    fn get_result_type(&self, rhs: &TypeSymbol) -> Option<TypeSymbol> {
+        match rhs {
+            TypeSymbol::Boolean => Some(TypeSymbol::Boolean),
+            _ => None,
+        }
+    }
+
+    fn operate(
+        &self,
+        lhs: &BoolType,
+        rhs: &dyn Type,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        assert_eq!(destination.get_type_symbol(), TypeSymbol::Boolean);
+        assert_eq!(rhs.get_type_symbol(), TypeSymbol::Boolean);
+
+        //? Booleans are always all ones or all zeros so xor is inequality
+        let (address_from, length) = (lhs.get_address(), lhs.get_length());
+        NotEqualInstruction::new_alloc(
+            program_memory,
+            address_from,
+            rhs.get_address(),
+            destination.get_address(),
+            length,
+        );
+        Ok(())
+    }
+}
+
+pub struct BoolEqual {}
+
+impl Operation<BoolType> for BoolEqual {
+    fn get_symbol(&self) -> Operator {
+        Operator::Equal
+    }
+
+    fn get_result_type(&self, rhs: &TypeSymbol) -> Option<TypeSymbol> {
+        match rhs {
+            TypeSymbol::Boolean => Some(TypeSymbol::Boolean),
+            _ => None,
+        }
+    }
+
+    fn operate(
+        &self,
+        lhs: &BoolType,
+        rhs: &dyn Type,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        assert_eq!(destination.get_type_symbol(), TypeSymbol::Boolean);
+        assert_eq!(rhs.get_type_symbol(), TypeSymbol::Boolean);
+
+        let (address_from, length) = (lhs.get_address(), lhs.get_length());
+        EqualityInstruction::new_alloc(
+            program_memory,
+            address_from,
+            rhs.get_address(),
+            destination.get_address(),
+            length,
+        );
+        Ok(())
+    }
+}
+
+pub struct BoolNotEqual {}
+
+impl Operation<BoolType> for BoolNotEqual {
+    fn get_symbol(&self) -> Operator {
+        Operator::NotEqual
+    }
+
+    fn get_result_type(&self, rhs: &TypeSymbol) -> Option<TypeSymbol> {
+        match rhs {
+            TypeSymbol::Boolean => Some(TypeSymbol::Boolean),
+            _ => None,
+        }
+    }
+
+    fn operate(
+        &self,
+        lhs: &BoolType,
+        rhs: &dyn Type,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        assert_eq!(destination.get_type_symbol(), TypeSymbol::Boolean);
+        assert_eq!(rhs.get_type_symbol(), TypeSymbol::Boolean);
+
+        let (address_from, length) = (lhs.get_address(), lhs.get_length());
+        NotEqualInstruction::new_alloc(
+            program_memory,
+            address_from,
+            rhs.get_address(),
+            destination.get_address(),
+            length,
+        );
+        Ok(())
+    }
+}
+
 pub struct BoolNot {}
 
 impl PrefixOperation<BoolType> for BoolNot {
     fn get_symbol(&self) -> Operator {
-        Operator::And
+        Operator::Not
     }
 
     fn get_result_type(&self) -> Option<TypeSymbol> {
@@ -0,0 +1,267 @@
+use crate::address::Address;
+use crate::bx;
+use crate::default_type_wrapper_struct_and_impl;
+use crate::errors::create_literal_not_impl_error;
+use crate::memory::MemoryManager;
+use crate::processing::blocks::StackSizes;
+use crate::processing::instructions::binary_not_7::BinaryNotInstruction;
+use crate::processing::instructions::copy_3::CopyInstruction;
+use crate::processing::instructions::equality_14::EqualityInstruction;
+use crate::processing::instructions::less_than_20::LessThanInstruction;
+use crate::processing::instructions::not_equal_15::NotEqualInstruction;
+use crate::processing::symbols::{Literal, Operator, TypeSymbol};
+use crate::processing::types::boolean::{BOOLEAN_SIZE, BOOL_FALSE, BOOL_TRUE};
+use crate::processing::types::Type;
+use crate::util::warn;
+
+default_type_wrapper_struct_and_impl!(CharWrapper, CharType, TypeSymbol::Character);
+
+pub const CHAR_SIZE: usize = 1;
+
+/// A single byte character. Ordered by character code
+pub struct CharType {
+    address: Option<Address>,
+}
+
+impl CharType {
+    pub fn new() -> CharType {
+        CharType { address: None }
+    }
+}
+
+impl Default for CharType {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Type for CharType {
+    fn get_type_symbol(&self) -> TypeSymbol {
+        TypeSymbol::Character
+    }
+
+    fn allocate_variable(
+        &mut self,
+        stack: &mut StackSizes,
+        _program_memory: &mut MemoryManager,
+    ) -> Result<(), String> {
+        if self.address.is_some() {
+            warn(
+                format!(
+                    "Allocating {:?} when it already has a memory address",
+                    self.get_type_symbol()
+                )
+                .as_str(),
+            )
+        }
+        self.address = Some(Address::StackDirect(stack.increment_stack_size(CHAR_SIZE)));
+
+        Ok(())
+    }
+
+    fn get_constant(&self, literal: &Literal) -> Result<Address, String> {
+        match literal {
+            Literal::Char(value) => match u8::try_from(*value) {
+                Ok(value) => Ok(Address::Immediate(vec![value])),
+                Err(_) => Err(format!(
+                    "The value ('{}') can't fit into a {} (only single byte characters are supported)",
+                    value,
+                    self.get_type_symbol()
+                )),
+            },
+            other => create_literal_not_impl_error(other, self.get_type_symbol()),
+        }
+    }
+
+    fn runtime_copy_from(
+        &self,
+        other: &dyn Type,
+        program_memory: &mut MemoryManager,
+    ) -> Result<CopyInstruction, String> {
+        match other.get_type_symbol() {
+            TypeSymbol::Character => Ok(CopyInstruction::new_alloc(
+                program_memory,
+                other.get_address(),
+                self.address.as_ref().unwrap(),
+                CHAR_SIZE,
+            )),
+            s => Err(format!(
+                "Copy not implemented from type '{}' to '{}'",
+                s,
+                TypeSymbol::Character
+            )),
+        }
+    }
+
+    fn runtime_copy_from_literal(
+        &self,
+        literal: &Literal,
+        program_memory: &mut MemoryManager,
+    ) -> Result<CopyInstruction, String> {
+        let constant = self.get_constant(literal)?;
+
+        Ok(CopyInstruction::new_alloc(
+            program_memory,
+            &constant,
+            self.address.as_ref().unwrap(),
+            CHAR_SIZE,
+        ))
+    }
+
+    fn get_prefix_operation_result_type(&self, _operator: &Operator) -> Vec<TypeSymbol> {
+        Vec::new()
+    }
+
+    fn get_operation_result_type(&self, operator: &Operator, rhs: &TypeSymbol) -> Vec<TypeSymbol> {
+        match (operator, rhs) {
+            (
+                Operator::Equal
+                | Operator::NotEqual
+                | Operator::Greater
+                | Operator::Less
+                | Operator::GreaterEqual
+                | Operator::LessEqual,
+                TypeSymbol::Character,
+            ) => vec![TypeSymbol::Boolean],
+            _ => Vec::new(),
+        }
+    }
+
+    fn operate_prefix(
+        &self,
+        operator: &Operator,
+        _destination: &dyn Type,
+        _program_memory: &mut MemoryManager,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        Err(format!(
+            "Operator {} not supported on {}",
+            operator,
+            self.get_type_symbol()
+        ))
+    }
+
+    fn operate(
+        &self,
+        operator: &Operator,
+        rhs: &dyn Type,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        if self
+            .get_operation_result_type(operator, &rhs.get_type_symbol())
+            .is_empty()
+        {
+            return Err(format!(
+                "Operator {} not supported between {} and {}",
+                operator,
+                self.get_type_symbol(),
+                rhs.get_type_symbol()
+            ));
+        }
+        assert_eq!(destination.get_type_symbol(), TypeSymbol::Boolean);
+
+        let (lhs, rhs) = (self.get_address(), rhs.get_address());
+        //? Greater comparisons swap the operands, inclusive comparisons invert the opposite
+        let (lhs, rhs, invert) = match operator {
+            Operator::Equal => {
+                EqualityInstruction::new_alloc(
+                    program_memory,
+                    lhs,
+                    rhs,
+                    destination.get_address(),
+                    CHAR_SIZE,
+                );
+                return Ok(());
+            }
+            Operator::NotEqual => {
+                NotEqualInstruction::new_alloc(
+                    program_memory,
+                    lhs,
+                    rhs,
+                    destination.get_address(),
+                    CHAR_SIZE,
+                );
+                return Ok(());
+            }
+            Operator::Less => (lhs, rhs, false),
+            Operator::Greater => (rhs, lhs, false),
+            Operator::GreaterEqual => (lhs, rhs, true),
+            Operator::LessEqual => (rhs, lhs, true),
+            _ => unreachable!(),
+        };
+
+        LessThanInstruction::new_alloc(
+            program_memory,
+            lhs,
+            rhs,
+            destination.get_address(),
+            CHAR_SIZE,
+            false,
+        );
+        if invert {
+            BinaryNotInstruction::new_alloc(
+                program_memory,
+                destination.get_address(),
+                destination.get_address(),
+                BOOLEAN_SIZE,
+            );
+        }
+        Ok(())
+    }
+
+    fn fold_operation(
+        &self,
+        operator: &Operator,
+        rhs: &dyn Type,
+    ) -> Result<Option<Address>, String> {
+        let (Address::Immediate(lhs_data), Address::Immediate(rhs_data)) =
+            (self.get_address(), rhs.get_address())
+        else {
+            return Ok(None);
+        };
+        if rhs.get_type_symbol() != TypeSymbol::Character {
+            return Ok(None);
+        }
+
+        let (lhs, rhs) = (lhs_data[0], rhs_data[0]);
+        let value = match operator {
+            Operator::Equal => lhs == rhs,
+            Operator::NotEqual => lhs != rhs,
+            Operator::Greater => lhs > rhs,
+            Operator::Less => lhs < rhs,
+            Operator::GreaterEqual => lhs >= rhs,
+            Operator::LessEqual => lhs <= rhs,
+            _ => return Ok(None),
+        };
+
+        Ok(Some(Address::Immediate(vec![if value {
+            BOOL_TRUE
+        } else {
+            BOOL_FALSE
+        }])))
+    }
+
+    fn get_address(&self) -> &Address {
+        self.address.as_ref().unwrap()
+    }
+
+    fn get_length(&self) -> usize {
+        CHAR_SIZE
+    }
+
+    fn get_address_mut(&mut self) -> &mut Address {
+        self.address.as_mut().unwrap()
+    }
+
+    fn set_address(&mut self, address: Address) {
+        self.address = Some(address);
+    }
+
+    fn duplicate(&self) -> Box<dyn Type> {
+        bx!(CharType {
+            address: self.address.as_ref().cloned(),
+        })
+    }
+}
@@ -0,0 +1,294 @@
+use crate::address::Address;
+use crate::errors::create_literal_not_impl_error;
+use crate::memory::MemoryManager;
+use crate::processing::blocks::StackSizes;
+use crate::processing::instructions::copy_3::CopyInstruction;
+use crate::processing::instructions::equality_14::EqualityInstruction;
+use crate::processing::symbols::{Literal, Operator, TypeSymbol};
+use crate::processing::types::{Type, TypeFactory};
+use crate::util::warn;
+use crate::bx;
+
+pub const ENUM_TAG_SIZE: usize = 1;
+
+/// The variants of a declared enum, in declaration order. A variant's tag is its index
+#[derive(Clone)]
+pub struct EnumDefinition {
+    pub name: String,
+    pub variants: Vec<(String, Option<TypeSymbol>)>,
+}
+
+impl EnumDefinition {
+    pub fn new(name: String) -> EnumDefinition {
+        EnumDefinition {
+            name,
+            variants: Vec::new(),
+        }
+    }
+
+    pub fn add_variant(&mut self, name: String, payload: Option<TypeSymbol>) -> Result<(), String> {
+        if self.get_variant(&name).is_some() {
+            return Err(format!(
+                "Variant '{}' is already declared in enum '{}'",
+                name, self.name
+            ));
+        }
+
+        if self.variants.len() > u8::MAX as usize {
+            return Err(format!(
+                "Enum '{}' cannot have more than {} variants",
+                self.name,
+                u8::MAX as usize + 1
+            ));
+        }
+
+        if let Some(payload) = &payload {
+            // ? Ensure the payload can be stored
+            TypeFactory::get_unallocated_type(payload)?;
+        }
+
+        self.variants.push((name, payload));
+        Ok(())
+    }
+
+    /// Returns the tag and payload type of a variant
+    pub fn get_variant(&self, name: &str) -> Option<(u8, Option<TypeSymbol>)> {
+        self.variants
+            .iter()
+            .position(|(variant, _)| variant == name)
+            .map(|i| (i as u8, self.variants[i].1))
+    }
+
+    pub fn get_payload_size(&self) -> usize {
+        self.variants
+            .iter()
+            .filter_map(|(_, payload)| *payload)
+            .map(|payload| {
+                TypeFactory::get_unallocated_type(&payload)
+                    .unwrap()
+                    .get_length()
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn get_size(&self) -> usize {
+        ENUM_TAG_SIZE + self.get_payload_size()
+    }
+}
+
+/// A tag followed by space for the largest variant payload
+pub struct EnumType {
+    definition: EnumDefinition,
+    address: Option<Address>,
+}
+
+impl EnumType {
+    pub fn new(definition: EnumDefinition) -> EnumType {
+        EnumType {
+            definition,
+            address: None,
+        }
+    }
+
+    pub fn get_tag_address(&self) -> &Address {
+        self.get_address()
+    }
+
+    pub fn get_payload_address(&self) -> Result<Address, String> {
+        self.get_address()
+            .offset(ENUM_TAG_SIZE)
+            .ok_or("Enum payloads can only be accessed through a direct address".to_string())
+    }
+
+    /// Creates a type pointing at the payload of `variant`. The payload is only valid if the
+    /// enum currently holds `variant`
+    pub fn get_payload_view(&self, variant: &str) -> Result<Option<Box<dyn Type>>, String> {
+        let Some((_, payload)) = self.definition.get_variant(variant) else {
+            return Err(format!(
+                "'{}' is not a variant of enum '{}'",
+                variant, self.definition.name
+            ));
+        };
+
+        let Some(payload) = payload else {
+            return Ok(None);
+        };
+
+        let mut view = TypeFactory::get_unallocated_type(&payload)?;
+        view.set_address(self.get_payload_address()?);
+        Ok(Some(view))
+    }
+
+    /// Writes `variant`'s tag into the enum. The payload must be written separately
+    pub fn set_variant(
+        &self,
+        variant: &str,
+        program_memory: &mut MemoryManager,
+    ) -> Result<(), String> {
+        let Some((tag, _)) = self.definition.get_variant(variant) else {
+            return Err(format!(
+                "'{}' is not a variant of enum '{}'",
+                variant, self.definition.name
+            ));
+        };
+
+        CopyInstruction::new_alloc(
+            program_memory,
+            &Address::Immediate(vec![tag]),
+            self.get_tag_address(),
+            ENUM_TAG_SIZE,
+        );
+        Ok(())
+    }
+
+    /// Sets `destination` to whether the enum currently holds `variant`
+    pub fn is_variant(
+        &self,
+        variant: &str,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+    ) -> Result<(), String> {
+        assert_eq!(destination.get_type_symbol(), TypeSymbol::Boolean);
+
+        let Some((tag, _)) = self.definition.get_variant(variant) else {
+            return Err(format!(
+                "'{}' is not a variant of enum '{}'",
+                variant, self.definition.name
+            ));
+        };
+
+        EqualityInstruction::new_alloc(
+            program_memory,
+            self.get_tag_address(),
+            &Address::Immediate(vec![tag]),
+            destination.get_address(),
+            ENUM_TAG_SIZE,
+        );
+        Ok(())
+    }
+}
+
+impl Type for EnumType {
+    fn get_type_symbol(&self) -> TypeSymbol {
+        TypeSymbol::enum_named(&self.definition.name)
+    }
+
+    fn allocate_variable(
+        &mut self,
+        stack: &mut StackSizes,
+        _program_memory: &mut MemoryManager,
+    ) -> Result<(), String> {
+        if self.address.is_some() {
+            warn(
+                format!(
+                    "Allocating {:?} when it already has a memory address",
+                    self.get_type_symbol()
+                )
+                .as_str(),
+            )
+        }
+        self.address = Some(Address::StackDirect(
+            stack.increment_stack_size(self.get_length()),
+        ));
+
+        Ok(())
+    }
+
+    fn get_constant(&self, literal: &Literal) -> Result<Address, String> {
+        create_literal_not_impl_error(literal, self.get_type_symbol())
+    }
+
+    fn runtime_copy_from(
+        &self,
+        other: &dyn Type,
+        program_memory: &mut MemoryManager,
+    ) -> Result<CopyInstruction, String> {
+        let other_symbol = other.get_type_symbol();
+        if other_symbol != self.get_type_symbol() {
+            let other_name = match other_symbol.get_enum_name() {
+                Some(name) => format!("enum '{}'", name),
+                None => format!("type '{}'", other_symbol),
+            };
+            return Err(format!(
+                "Copy not implemented from {} to enum '{}'",
+                other_name, self.definition.name
+            ));
+        }
+
+        Ok(CopyInstruction::new_alloc(
+            program_memory,
+            other.get_address(),
+            self.address.as_ref().unwrap(),
+            self.get_length(),
+        ))
+    }
+
+    fn runtime_copy_from_literal(
+        &self,
+        literal: &Literal,
+        _program_memory: &mut MemoryManager,
+    ) -> Result<CopyInstruction, String> {
+        create_literal_not_impl_error(literal, self.get_type_symbol())
+    }
+
+    fn get_prefix_operation_result_type(&self, _operator: &Operator) -> Vec<TypeSymbol> {
+        Vec::new()
+    }
+
+    fn get_operation_result_type(&self, _operator: &Operator, _rhs: &TypeSymbol) -> Vec<TypeSymbol> {
+        Vec::new()
+    }
+
+    fn operate_prefix(
+        &self,
+        operator: &Operator,
+        _destination: &dyn Type,
+        _program_memory: &mut MemoryManager,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        Err(format!(
+            "Operator {} not supported on {} (use a match statement)",
+            operator,
+            self.get_type_symbol()
+        ))
+    }
+
+    fn operate(
+        &self,
+        operator: &Operator,
+        rhs: &dyn Type,
+        _destination: &dyn Type,
+        _program_memory: &mut MemoryManager,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        Err(format!(
+            "Operator {} not supported between {} and {} (use a match statement)",
+            operator,
+            self.get_type_symbol(),
+            rhs.get_type_symbol()
+        ))
+    }
+
+    fn get_address(&self) -> &Address {
+        self.address.as_ref().unwrap()
+    }
+
+    fn get_length(&self) -> usize {
+        self.definition.get_size()
+    }
+
+    fn get_address_mut(&mut self) -> &mut Address {
+        self.address.as_mut().unwrap()
+    }
+
+    fn set_address(&mut self, address: Address) {
+        self.address = Some(address);
+    }
+
+    fn duplicate(&self) -> Box<dyn Type> {
+        let mut t = EnumType::new(self.definition.clone());
+        t.address = self.address.as_ref().cloned();
+        bx!(t)
+    }
+}
@@ -0,0 +1,360 @@
+use crate::address::Address;
+use crate::bx;
+use crate::default_type_wrapper_struct_and_impl;
+use crate::errors::create_literal_not_impl_error;
+use crate::memory::MemoryManager;
+use crate::processing::blocks::StackSizes;
+use crate::processing::instructions::binary_not_7::BinaryNotInstruction;
+use crate::processing::instructions::bitwise_25::{BitwiseInstruction, BitwiseOperation};
+use crate::processing::instructions::copy_3::CopyInstruction;
+use crate::processing::instructions::float_convert_23::FloatConvertInstruction;
+use crate::processing::instructions::float_operation_22::{
+    read_float, FloatOperation, FloatOperationInstruction, FLOAT_BYTES,
+};
+use crate::processing::symbols::{Literal, Operator, TypeSymbol};
+use crate::processing::types::boolean::{BOOLEAN_SIZE, BOOL_FALSE, BOOL_TRUE};
+use crate::processing::types::Type;
+use crate::util::warn;
+
+/// Integers with a larger magnitude than this (2^53) can't all be represented by a float
+pub const MAX_EXACT_FLOAT_INT: i128 = 1 << 53;
+
+default_type_wrapper_struct_and_impl!(FloatWrapper, FloatType, TypeSymbol::Float);
+
+/// A 64-bit IEEE-754 float
+pub struct FloatType {
+    address: Option<Address>,
+}
+
+impl FloatType {
+    pub fn new() -> FloatType {
+        FloatType { address: None }
+    }
+
+    /// Converts an integer of any width into `self`. Used for `as` casts
+    pub fn convert_from(
+        &self,
+        other: &dyn Type,
+        program_memory: &mut MemoryManager,
+    ) -> Result<(), String> {
+        let Some((size, signed)) = other.get_type_symbol().get_integer_format() else {
+            return Err(format!(
+                "Cannot cast '{}' to '{}'",
+                other.get_type_symbol(),
+                TypeSymbol::Float
+            ));
+        };
+
+        FloatConvertInstruction::new_alloc(
+            program_memory,
+            other.get_address(),
+            self.get_address(),
+            true,
+            size,
+            signed,
+        );
+        Ok(())
+    }
+
+    /// Converts `self` into an integer of any width, truncating towards zero and saturating at
+    /// the integer's bounds. Used for `as` casts
+    pub fn convert_into(
+        &self,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+    ) -> Result<(), String> {
+        let Some((size, signed)) = destination.get_type_symbol().get_integer_format() else {
+            return Err(format!(
+                "Cannot cast '{}' to '{}'",
+                TypeSymbol::Float,
+                destination.get_type_symbol()
+            ));
+        };
+
+        FloatConvertInstruction::new_alloc(
+            program_memory,
+            self.get_address(),
+            destination.get_address(),
+            false,
+            size,
+            signed,
+        );
+        Ok(())
+    }
+}
+
+impl Default for FloatType {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Type for FloatType {
+    fn get_type_symbol(&self) -> TypeSymbol {
+        TypeSymbol::Float
+    }
+
+    fn allocate_variable(
+        &mut self,
+        stack: &mut StackSizes,
+        _program_memory: &mut MemoryManager,
+    ) -> Result<(), String> {
+        if self.address.is_some() {
+            warn(
+                format!(
+                    "Allocating {:?} when it already has a memory address",
+                    self.get_type_symbol()
+                )
+                .as_str(),
+            )
+        }
+        self.address = Some(Address::StackDirect(
+            stack.increment_stack_size(FLOAT_BYTES),
+        ));
+
+        Ok(())
+    }
+
+    fn get_constant(&self, literal: &Literal) -> Result<Address, String> {
+        match literal {
+            Literal::Float(value) => Ok(Address::Immediate(Vec::from(value.to_le_bytes()))),
+            Literal::Int(value) if value.abs() > MAX_EXACT_FLOAT_INT => Err(format!(
+                "The value ({}) can't be represented exactly by a {} (the value must be between -{} and {})",
+                value,
+                self.get_type_symbol(),
+                MAX_EXACT_FLOAT_INT,
+                MAX_EXACT_FLOAT_INT
+            )),
+            Literal::Int(value) => Ok(Address::Immediate(Vec::from((*value as f64).to_le_bytes()))),
+            other => create_literal_not_impl_error(other, self.get_type_symbol()),
+        }
+    }
+
+    fn runtime_copy_from(
+        &self,
+        other: &dyn Type,
+        program_memory: &mut MemoryManager,
+    ) -> Result<CopyInstruction, String> {
+        if other.get_type_symbol() != TypeSymbol::Float {
+            return Err(format!(
+                "Copy not implemented from type '{}' to '{}' (use as syntax)",
+                other.get_type_symbol(),
+                TypeSymbol::Float
+            ));
+        }
+
+        Ok(CopyInstruction::new_alloc(
+            program_memory,
+            other.get_address(),
+            self.address.as_ref().unwrap(),
+            FLOAT_BYTES,
+        ))
+    }
+
+    fn runtime_copy_from_literal(
+        &self,
+        literal: &Literal,
+        program_memory: &mut MemoryManager,
+    ) -> Result<CopyInstruction, String> {
+        let constant = self.get_constant(literal)?;
+
+        Ok(CopyInstruction::new_alloc(
+            program_memory,
+            &constant,
+            self.address.as_ref().unwrap(),
+            FLOAT_BYTES,
+        ))
+    }
+
+    fn get_prefix_operation_result_type(&self, operator: &Operator) -> Vec<TypeSymbol> {
+        match operator {
+            Operator::Negate => vec![TypeSymbol::Float],
+            _ => Vec::new(),
+        }
+    }
+
+    fn get_operation_result_type(&self, operator: &Operator, rhs: &TypeSymbol) -> Vec<TypeSymbol> {
+        if *rhs != TypeSymbol::Float {
+            return Vec::new();
+        }
+
+        match operator {
+            Operator::Add | Operator::Subtract | Operator::Product | Operator::Divide => {
+                vec![TypeSymbol::Float]
+            }
+            Operator::Greater
+            | Operator::Less
+            | Operator::GreaterEqual
+            | Operator::LessEqual
+            | Operator::Equal
+            | Operator::NotEqual => vec![TypeSymbol::Boolean],
+            _ => Vec::new(),
+        }
+    }
+
+    fn operate_prefix(
+        &self,
+        operator: &Operator,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        if *operator != Operator::Negate {
+            return Err(format!(
+                "Operator {} not supported on {}",
+                operator,
+                self.get_type_symbol()
+            ));
+        }
+        assert_eq!(destination.get_type_symbol(), TypeSymbol::Float);
+
+        //? Flip the sign bit so -0.0 and NaNs are handled as IEEE-754 negation
+        BitwiseInstruction::new_alloc(
+            program_memory,
+            BitwiseOperation::Xor,
+            self.get_address(),
+            &Address::Immediate(Vec::from((-0.0f64).to_le_bytes())),
+            destination.get_address(),
+            FLOAT_BYTES,
+        );
+        Ok(())
+    }
+
+    fn fold_prefix_operation(&self, operator: &Operator) -> Result<Option<Address>, String> {
+        match (operator, self.get_address()) {
+            (Operator::Negate, Address::Immediate(data)) => Ok(Some(Address::Immediate(
+                Vec::from((-read_float(data)).to_le_bytes()),
+            ))),
+            _ => Ok(None),
+        }
+    }
+
+    fn operate(
+        &self,
+        operator: &Operator,
+        rhs: &dyn Type,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        let Some(result_type) = self
+            .get_operation_result_type(operator, &rhs.get_type_symbol())
+            .first()
+            .copied()
+        else {
+            return Err(format!(
+                "Operator {} not supported between {} and {}",
+                operator,
+                self.get_type_symbol(),
+                rhs.get_type_symbol()
+            ));
+        };
+        assert_eq!(destination.get_type_symbol(), result_type);
+
+        //? Greater comparisons swap the operands, NotEqual inverts Equal
+        let (lhs, rhs) = (self.get_address(), rhs.get_address());
+        let (operation, lhs, rhs) = match operator {
+            Operator::Add => (FloatOperation::Add, lhs, rhs),
+            Operator::Subtract => (FloatOperation::Subtract, lhs, rhs),
+            Operator::Product => (FloatOperation::Multiply, lhs, rhs),
+            Operator::Divide => (FloatOperation::Divide, lhs, rhs),
+            Operator::Less => (FloatOperation::Less, lhs, rhs),
+            Operator::LessEqual => (FloatOperation::LessEqual, lhs, rhs),
+            Operator::Greater => (FloatOperation::Less, rhs, lhs),
+            Operator::GreaterEqual => (FloatOperation::LessEqual, rhs, lhs),
+            Operator::Equal | Operator::NotEqual => (FloatOperation::Equal, lhs, rhs),
+            _ => unreachable!(),
+        };
+
+        FloatOperationInstruction::new_alloc(
+            program_memory,
+            operation,
+            lhs,
+            rhs,
+            destination.get_address(),
+        );
+
+        if *operator == Operator::NotEqual {
+            BinaryNotInstruction::new_alloc(
+                program_memory,
+                destination.get_address(),
+                destination.get_address(),
+                BOOLEAN_SIZE,
+            );
+        }
+        Ok(())
+    }
+
+    fn fold_operation(
+        &self,
+        operator: &Operator,
+        rhs: &dyn Type,
+    ) -> Result<Option<Address>, String> {
+        let (Address::Immediate(lhs_data), Address::Immediate(rhs_data)) =
+            (self.get_address(), rhs.get_address())
+        else {
+            return Ok(None);
+        };
+        if rhs.get_type_symbol() != TypeSymbol::Float {
+            return Ok(None);
+        }
+
+        let lhs = read_float(lhs_data);
+        let rhs = read_float(rhs_data);
+
+        let comparison = |result: bool| {
+            Ok(Some(Address::Immediate(vec![if result {
+                BOOL_TRUE
+            } else {
+                BOOL_FALSE
+            }])))
+        };
+        let value = match operator {
+            Operator::Add => lhs + rhs,
+            Operator::Subtract => lhs - rhs,
+            Operator::Product => lhs * rhs,
+            Operator::Divide => lhs / rhs,
+            Operator::Greater => return comparison(lhs > rhs),
+            Operator::Less => return comparison(lhs < rhs),
+            Operator::GreaterEqual => return comparison(lhs >= rhs),
+            Operator::LessEqual => return comparison(lhs <= rhs),
+            Operator::Equal => return comparison(lhs == rhs),
+            Operator::NotEqual => return comparison(lhs != rhs),
+            _ => return Ok(None),
+        };
+
+        if value.is_infinite() && lhs.is_finite() && rhs.is_finite() {
+            return Err(format!(
+                "Constant {} of {} and {} overflows {}",
+                operator,
+                lhs,
+                rhs,
+                TypeSymbol::Float
+            ));
+        }
+
+        Ok(Some(Address::Immediate(Vec::from(value.to_le_bytes()))))
+    }
+
+    fn get_address(&self) -> &Address {
+        self.address.as_ref().unwrap()
+    }
+
+    fn get_length(&self) -> usize {
+        FLOAT_BYTES
+    }
+
+    fn get_address_mut(&mut self) -> &mut Address {
+        self.address.as_mut().unwrap()
+    }
+
+    fn set_address(&mut self, address: Address) {
+        self.address = Some(address);
+    }
+
+    fn duplicate(&self) -> Box<dyn Type> {
+        bx!(FloatType {
+            address: self.address.as_ref().cloned(),
+        })
+    }
+}
@@ -0,0 +1,615 @@
+use crate::address::Address;
+use crate::bx;
+use crate::errors::create_literal_not_impl_error;
+use crate::memory::MemoryManager;
+use crate::processing::blocks::StackSizes;
+use crate::processing::instructions::add_instruction_13::AddInstruction;
+use crate::processing::instructions::binary_not_7::BinaryNotInstruction;
+use crate::processing::instructions::bitwise_25::{BitwiseInstruction, BitwiseOperation};
+use crate::processing::instructions::copy_3::CopyInstruction;
+use crate::processing::instructions::divide_19::DivideInstruction;
+use crate::processing::instructions::equality_14::EqualityInstruction;
+use crate::processing::instructions::extend_21::ExtendInstruction;
+use crate::processing::instructions::less_than_20::LessThanInstruction;
+use crate::processing::instructions::modulo_27::ModuloInstruction;
+use crate::processing::instructions::multiply_18::MultiplyInstruction;
+use crate::processing::instructions::not_equal_15::NotEqualInstruction;
+use crate::processing::instructions::panic_if_not_17::PanicIfNotInstruction;
+use crate::processing::instructions::shift_26::ShiftInstruction;
+use crate::processing::symbols::{Literal, Operator, TypeSymbol};
+use crate::processing::types::boolean::{BoolType, BOOLEAN_SIZE, BOOL_FALSE, BOOL_TRUE};
+use crate::processing::types::{Type, UninstantiatedType};
+use crate::util::{int_to_bytes, read_int, warn};
+
+pub struct IntegerWrapper {
+    type_symbol: TypeSymbol,
+}
+
+impl IntegerWrapper {
+    pub fn new(type_symbol: TypeSymbol) -> IntegerWrapper {
+        IntegerWrapper { type_symbol }
+    }
+}
+
+impl UninstantiatedType for IntegerWrapper {
+    fn instantiate(&self) -> Box<dyn Type> {
+        bx!(IntegerType::new(self.type_symbol))
+    }
+
+    fn get_type_symbol(&self) -> TypeSymbol {
+        self.type_symbol
+    }
+}
+
+/// Any of the fixed-width integers (`int`, `i8`..`i64`, `u8`..`u64`). Stored little-endian in
+/// two's complement
+pub struct IntegerType {
+    type_symbol: TypeSymbol,
+    size: usize,
+    signed: bool,
+    address: Option<Address>,
+}
+
+impl IntegerType {
+    pub fn new(type_symbol: TypeSymbol) -> IntegerType {
+        let (size, signed) = type_symbol
+            .get_integer_format()
+            .expect("IntegerType created from a non-integer type");
+
+        IntegerType {
+            type_symbol,
+            size,
+            signed,
+            address: None,
+        }
+    }
+
+    /// Returns the range of values this type can hold
+    fn get_range(&self) -> (i128, i128) {
+        let bits = self.size as u32 * 8;
+        if self.signed {
+            (-(1i128 << (bits - 1)), (1i128 << (bits - 1)) - 1)
+        } else {
+            (0, (1i128 << bits) - 1)
+        }
+    }
+
+    /// Copies an integer of any width into `self`, sign-extending, zero-extending or truncating
+    /// it as required. Used for `as` casts - assignment only accepts the same type
+    pub fn convert_from(
+        &self,
+        other: &dyn Type,
+        program_memory: &mut MemoryManager,
+    ) -> Result<(), String> {
+        let Some((other_size, other_signed)) = other.get_type_symbol().get_integer_format() else {
+            return Err(format!(
+                "Cannot cast '{}' to '{}'",
+                other.get_type_symbol(),
+                self.type_symbol
+            ));
+        };
+
+        ExtendInstruction::new_alloc(
+            program_memory,
+            other.get_address(),
+            other_size,
+            self.get_address(),
+            self.size,
+            other_signed,
+        );
+        Ok(())
+    }
+
+    fn subtract(
+        &self,
+        rhs: &dyn Type,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        let mut magic_number = IntegerType::new(self.type_symbol);
+        magic_number.allocate_variable(stack_sizes, program_memory)?;
+        //? Two's complement of the subtracted value
+        BinaryNotInstruction::new_alloc(
+            program_memory,
+            rhs.get_address(),
+            magic_number.get_address(),
+            self.size,
+        );
+        AddInstruction::new_alloc(
+            program_memory,
+            magic_number.get_address(),
+            &Address::Immediate(int_to_bytes(1, self.size)),
+            magic_number.get_address(),
+            self.size,
+        );
+
+        AddInstruction::new_alloc(
+            program_memory,
+            self.get_address(),
+            magic_number.get_address(),
+            destination.get_address(),
+            self.size,
+        );
+        Ok(())
+    }
+
+    /// Checks that the divisor of a division or modulo isn't zero, at compile-time if possible
+    fn check_divisor(
+        &self,
+        rhs: &dyn Type,
+        program_memory: &mut MemoryManager,
+        stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        if let Address::Immediate(data) = rhs.get_address() {
+            if data.iter().all(|b| *b == 0) {
+                return Err("Division by zero".to_string());
+            }
+        } else {
+            let mut non_zero = BoolType::new();
+            non_zero.allocate_variable(stack_sizes, program_memory)?;
+            NotEqualInstruction::new_alloc(
+                program_memory,
+                rhs.get_address(),
+                &Address::Immediate(vec![0; self.size]),
+                non_zero.get_address(),
+                self.size,
+            );
+            PanicIfNotInstruction::new_alloc(
+                program_memory,
+                non_zero.get_address(),
+                &format!("Division by zero ({})", self.type_symbol),
+            );
+        }
+        Ok(())
+    }
+
+    fn divide(
+        &self,
+        rhs: &dyn Type,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        self.check_divisor(rhs, program_memory, stack_sizes)?;
+        DivideInstruction::new_alloc(
+            program_memory,
+            self.get_address(),
+            rhs.get_address(),
+            destination.get_address(),
+            self.size,
+            self.signed,
+        );
+        Ok(())
+    }
+
+    fn modulo(
+        &self,
+        rhs: &dyn Type,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        self.check_divisor(rhs, program_memory, stack_sizes)?;
+        ModuloInstruction::new_alloc(
+            program_memory,
+            self.get_address(),
+            rhs.get_address(),
+            destination.get_address(),
+            self.size,
+            self.signed,
+        );
+        Ok(())
+    }
+
+    /// Puts `lhs < rhs` into `destination`, inverting it afterwards if `invert`
+    fn less_than(
+        &self,
+        lhs: &Address,
+        rhs: &Address,
+        invert: bool,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+    ) {
+        LessThanInstruction::new_alloc(
+            program_memory,
+            lhs,
+            rhs,
+            destination.get_address(),
+            self.size,
+            self.signed,
+        );
+
+        if invert {
+            BinaryNotInstruction::new_alloc(
+                program_memory,
+                destination.get_address(),
+                destination.get_address(),
+                BOOLEAN_SIZE,
+            );
+        }
+    }
+}
+
+impl Type for IntegerType {
+    fn get_type_symbol(&self) -> TypeSymbol {
+        self.type_symbol
+    }
+
+    fn allocate_variable(
+        &mut self,
+        stack: &mut StackSizes,
+        _program_memory: &mut MemoryManager,
+    ) -> Result<(), String> {
+        if self.address.is_some() {
+            warn(
+                format!(
+                    "Allocating {:?} when it already has a memory address",
+                    self.get_type_symbol()
+                )
+                .as_str(),
+            )
+        }
+        self.address = Some(Address::StackDirect(stack.increment_stack_size(self.size)));
+
+        Ok(())
+    }
+
+    fn get_constant(&self, literal: &Literal) -> Result<Address, String> {
+        match literal {
+            Literal::TypedInt(_, t) if *t != self.get_type_symbol() => Err(format!(
+                "A {} literal can't be used as a {} (use as syntax)",
+                t,
+                self.get_type_symbol()
+            )),
+            Literal::Int(value) | Literal::TypedInt(value, _) => {
+                let value = *value;
+                let (min, max) = self.get_range();
+                if value < min || value > max {
+                    return Err(format!(
+                        "The value ({}) can't fit into a {} (the value must be between {} and {})",
+                        value,
+                        self.get_type_symbol(),
+                        min,
+                        max
+                    ));
+                }
+
+                Ok(Address::Immediate(int_to_bytes(value, self.size)))
+            }
+            other => create_literal_not_impl_error(other, self.get_type_symbol()),
+        }
+    }
+
+    fn runtime_copy_from(
+        &self,
+        other: &dyn Type,
+        program_memory: &mut MemoryManager,
+    ) -> Result<CopyInstruction, String> {
+        if other.get_type_symbol() != self.get_type_symbol() {
+            return Err(format!(
+                "Copy not implemented from type '{}' to '{}' (use as syntax)",
+                other.get_type_symbol(),
+                self.get_type_symbol()
+            ));
+        }
+
+        Ok(CopyInstruction::new_alloc(
+            program_memory,
+            other.get_address(),
+            self.address.as_ref().unwrap(),
+            self.size,
+        ))
+    }
+
+    fn runtime_copy_from_literal(
+        &self,
+        literal: &Literal,
+        program_memory: &mut MemoryManager,
+    ) -> Result<CopyInstruction, String> {
+        let constant = self.get_constant(literal)?;
+
+        Ok(CopyInstruction::new_alloc(
+            program_memory,
+            &constant,
+            self.address.as_ref().unwrap(),
+            self.size,
+        ))
+    }
+
+    fn get_prefix_operation_result_type(&self, operator: &Operator) -> Vec<TypeSymbol> {
+        match operator {
+            Operator::BitwiseNot => vec![self.type_symbol],
+            Operator::Negate if self.signed => vec![self.type_symbol],
+            _ => Vec::new(),
+        }
+    }
+
+    fn get_operation_result_type(&self, operator: &Operator, rhs: &TypeSymbol) -> Vec<TypeSymbol> {
+        if *rhs != self.type_symbol {
+            return Vec::new();
+        }
+
+        match operator {
+            Operator::Add
+            | Operator::Subtract
+            | Operator::Product
+            | Operator::Divide
+            | Operator::Modulo
+            | Operator::And
+            | Operator::Or
+            | Operator::Xor
+            | Operator::LeftShift
+            | Operator::RightShift => vec![self.type_symbol],
+            Operator::Greater
+            | Operator::Less
+            | Operator::GreaterEqual
+            | Operator::LessEqual
+            | Operator::Equal
+            | Operator::NotEqual => vec![TypeSymbol::Boolean],
+            _ => Vec::new(),
+        }
+    }
+
+    fn operate_prefix(
+        &self,
+        operator: &Operator,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        if self.get_prefix_operation_result_type(operator).is_empty() {
+            return Err(format!(
+                "Operator {} not supported on {}",
+                operator,
+                self.get_type_symbol()
+            ));
+        }
+        assert_eq!(destination.get_type_symbol(), self.type_symbol);
+
+        BinaryNotInstruction::new_alloc(
+            program_memory,
+            self.get_address(),
+            destination.get_address(),
+            self.size,
+        );
+        //? Negation is the two's complement - the bitwise not plus one
+        if *operator == Operator::Negate {
+            AddInstruction::new_alloc(
+                program_memory,
+                destination.get_address(),
+                &Address::Immediate(int_to_bytes(1, self.size)),
+                destination.get_address(),
+                self.size,
+            );
+        }
+        Ok(())
+    }
+
+    fn fold_prefix_operation(&self, operator: &Operator) -> Result<Option<Address>, String> {
+        match (operator, self.get_address()) {
+            (Operator::BitwiseNot, Address::Immediate(data)) => {
+                Ok(Some(Address::Immediate(data.iter().map(|b| !b).collect())))
+            }
+            (Operator::Negate, Address::Immediate(data)) if self.signed => {
+                let value = read_int(data, true);
+                let (min, max) = self.get_range();
+                if value == min {
+                    return Err(format!(
+                        "Constant negation of {} overflows {} (the value must be between {} and {})",
+                        value, self.type_symbol, min, max
+                    ));
+                }
+                Ok(Some(Address::Immediate(int_to_bytes(-value, self.size))))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn operate(
+        &self,
+        operator: &Operator,
+        rhs: &dyn Type,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        let Some(result_type) = self
+            .get_operation_result_type(operator, &rhs.get_type_symbol())
+            .first()
+            .copied()
+        else {
+            return Err(format!(
+                "Operator {} not supported between {} and {}",
+                operator,
+                self.get_type_symbol(),
+                rhs.get_type_symbol()
+            ));
+        };
+        assert_eq!(destination.get_type_symbol(), result_type);
+
+        let (lhs_address, rhs_address) = (self.get_address(), rhs.get_address());
+        match operator {
+            Operator::Add => {
+                AddInstruction::new_alloc(
+                    program_memory,
+                    lhs_address,
+                    rhs_address,
+                    destination.get_address(),
+                    self.size,
+                );
+            }
+            Operator::Subtract => self.subtract(rhs, destination, program_memory, stack_sizes)?,
+            Operator::Product => {
+                MultiplyInstruction::new_alloc(
+                    program_memory,
+                    lhs_address,
+                    rhs_address,
+                    destination.get_address(),
+                    self.size,
+                );
+            }
+            Operator::Divide => self.divide(rhs, destination, program_memory, stack_sizes)?,
+            Operator::Modulo => self.modulo(rhs, destination, program_memory, stack_sizes)?,
+            Operator::And | Operator::Or | Operator::Xor => {
+                let operation = match operator {
+                    Operator::And => BitwiseOperation::And,
+                    Operator::Or => BitwiseOperation::Or,
+                    _ => BitwiseOperation::Xor,
+                };
+                BitwiseInstruction::new_alloc(
+                    program_memory,
+                    operation,
+                    lhs_address,
+                    rhs_address,
+                    destination.get_address(),
+                    self.size,
+                );
+            }
+            Operator::LeftShift | Operator::RightShift => {
+                ShiftInstruction::new_alloc(
+                    program_memory,
+                    lhs_address,
+                    rhs_address,
+                    destination.get_address(),
+                    self.size,
+                    *operator == Operator::LeftShift,
+                    self.signed,
+                );
+            }
+            Operator::Less => {
+                self.less_than(lhs_address, rhs_address, false, destination, program_memory)
+            }
+            Operator::Greater => {
+                self.less_than(rhs_address, lhs_address, false, destination, program_memory)
+            }
+            Operator::GreaterEqual => {
+                self.less_than(lhs_address, rhs_address, true, destination, program_memory)
+            }
+            Operator::LessEqual => {
+                self.less_than(rhs_address, lhs_address, true, destination, program_memory)
+            }
+            Operator::Equal => {
+                EqualityInstruction::new_alloc(
+                    program_memory,
+                    lhs_address,
+                    rhs_address,
+                    destination.get_address(),
+                    self.size,
+                );
+            }
+            Operator::NotEqual => {
+                NotEqualInstruction::new_alloc(
+                    program_memory,
+                    lhs_address,
+                    rhs_address,
+                    destination.get_address(),
+                    self.size,
+                );
+            }
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+
+    fn fold_operation(
+        &self,
+        operator: &Operator,
+        rhs: &dyn Type,
+    ) -> Result<Option<Address>, String> {
+        let (Address::Immediate(lhs_data), Address::Immediate(rhs_data)) =
+            (self.get_address(), rhs.get_address())
+        else {
+            return Ok(None);
+        };
+        if rhs.get_type_symbol() != self.type_symbol {
+            return Ok(None);
+        }
+
+        let lhs = read_int(lhs_data, self.signed);
+        let rhs = read_int(rhs_data, self.signed);
+
+        let comparison = |result: bool| {
+            Ok(Some(Address::Immediate(vec![if result {
+                BOOL_TRUE
+            } else {
+                BOOL_FALSE
+            }])))
+        };
+        let value = match operator {
+            Operator::Add => lhs.checked_add(rhs),
+            Operator::Subtract => lhs.checked_sub(rhs),
+            Operator::Product => lhs.checked_mul(rhs),
+            Operator::Divide => {
+                if rhs == 0 {
+                    return Err("Division by zero".to_string());
+                }
+                lhs.checked_div(rhs)
+            }
+            Operator::Modulo => {
+                if rhs == 0 {
+                    return Err("Division by zero".to_string());
+                }
+                lhs.checked_rem(rhs)
+            }
+            //? Both operands are sign-extended the same way so the result is always in range
+            Operator::And => Some(lhs & rhs),
+            Operator::Or => Some(lhs | rhs),
+            Operator::Xor => Some(lhs ^ rhs),
+            //? Shifts discard bits the same way as at runtime rather than overflowing
+            Operator::LeftShift | Operator::RightShift => {
+                if rhs < 0 {
+                    return Err(format!("Cannot shift by a negative amount ({})", rhs));
+                }
+                let bits = self.size as i128 * 8;
+                let value = match (*operator == Operator::LeftShift, rhs >= bits) {
+                    (true, true) => 0,
+                    (true, false) => lhs << rhs,
+                    (false, true) => lhs >> 127,
+                    (false, false) => lhs >> rhs,
+                };
+                return Ok(Some(Address::Immediate(int_to_bytes(value, self.size))));
+            }
+            Operator::Greater => return comparison(lhs > rhs),
+            Operator::Less => return comparison(lhs < rhs),
+            Operator::GreaterEqual => return comparison(lhs >= rhs),
+            Operator::LessEqual => return comparison(lhs <= rhs),
+            Operator::Equal => return comparison(lhs == rhs),
+            Operator::NotEqual => return comparison(lhs != rhs),
+            _ => return Ok(None),
+        };
+
+        let (min, max) = self.get_range();
+        match value {
+            Some(value) if value >= min && value <= max => {
+                Ok(Some(Address::Immediate(int_to_bytes(value, self.size))))
+            }
+            _ => Err(format!(
+                "Constant {} of {} and {} overflows {} (the value must be between {} and {})",
+                operator, lhs, rhs, self.type_symbol, min, max
+            )),
+        }
+    }
+
+    fn get_address(&self) -> &Address {
+        self.address.as_ref().unwrap()
+    }
+
+    fn get_length(&self) -> usize {
+        self.size
+    }
+
+    fn get_address_mut(&mut self) -> &mut Address {
+        self.address.as_mut().unwrap()
+    }
+
+    fn set_address(&mut self, address: Address) {
+        self.address = Some(address);
+    }
+
+    fn duplicate(&self) -> Box<dyn Type> {
+        let mut t = IntegerType::new(self.type_symbol);
+        t.address = self.address.as_ref().cloned();
+        bx!(t)
+    }
+}
@@ -0,0 +1,394 @@
+use crate::address::Address;
+use crate::bx;
+use crate::memory::MemoryManager;
+use crate::processing::blocks::StackSizes;
+use crate::processing::instructions::binary_and_8::BinaryAndInstruction;
+use crate::processing::instructions::binary_not_7::BinaryNotInstruction;
+use crate::processing::instructions::binary_or_12::BinaryOrInstruction;
+use crate::processing::instructions::copy_3::CopyInstruction;
+use crate::processing::instructions::equality_14::EqualityInstruction;
+use crate::processing::instructions::panic_if_not_17::PanicIfNotInstruction;
+use crate::processing::symbols::{Literal, Operator, TypeSymbol};
+use crate::processing::types::boolean::{BoolType, BOOLEAN_SIZE, BOOL_FALSE, BOOL_TRUE};
+use crate::processing::types::{Type, TypeFactory};
+use crate::util::warn;
+
+/// An `opt<T>`. A presence flag followed by space for the value
+pub struct OptionalType {
+    inner: TypeSymbol,
+    inner_length: usize,
+    address: Option<Address>,
+}
+
+impl OptionalType {
+    pub fn new(inner: TypeSymbol) -> Result<OptionalType, String> {
+        if inner.get_optional_inner().is_some() {
+            return Err(format!("{} cannot be nested in an optional", inner));
+        }
+
+        Ok(OptionalType {
+            inner,
+            inner_length: TypeFactory::get_unallocated_type(&inner)?.get_length(),
+            address: None,
+        })
+    }
+
+    pub fn get_flag_address(&self) -> &Address {
+        self.get_address()
+    }
+
+    /// Creates a type referring to the stored value. The value is only valid if the flag is set
+    pub fn get_value_view(&self) -> Result<Box<dyn Type>, String> {
+        let Some(value_address) = self.get_address().offset(BOOLEAN_SIZE) else {
+            return Err(format!(
+                "{} values can only be accessed through a direct address",
+                self.get_type_symbol()
+            ));
+        };
+
+        let mut value = TypeFactory::get_unallocated_type(&self.inner)?;
+        value.set_address(value_address);
+        Ok(value)
+    }
+
+    /// Creates a type referring to the stored value, inserting a check that raises a runtime
+    /// error if there is no value
+    pub fn unwrap(&self, program_memory: &mut MemoryManager) -> Result<Box<dyn Type>, String> {
+        let value = self.get_value_view()?;
+
+        PanicIfNotInstruction::new_alloc(
+            program_memory,
+            self.get_flag_address(),
+            &format!("Unwrapped an empty {}", self.get_type_symbol()),
+        );
+
+        Ok(value)
+    }
+
+    /// Sets `destination` to whether the optionals are both empty or both hold equal values.
+    /// Values are compared with the inner type's `Equal` e.g. so that `-0.0` equals `0.0`
+    fn equals(
+        &self,
+        rhs: &dyn Type,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        let mut rhs_optional = OptionalType::new(self.inner)?;
+        rhs_optional.set_address(rhs.get_address().clone());
+        let lhs_value = self.get_value_view()?;
+        let rhs_value = rhs_optional.get_value_view()?;
+
+        let mut flags_equal = BoolType::new();
+        flags_equal.allocate_variable(stack_sizes, program_memory)?;
+        let mut values_equal = BoolType::new();
+        values_equal.allocate_variable(stack_sizes, program_memory)?;
+
+        EqualityInstruction::new_alloc(
+            program_memory,
+            &get_flag_of(self.get_address()),
+            &get_flag_of(rhs.get_address()),
+            flags_equal.get_address(),
+            BOOLEAN_SIZE,
+        );
+        lhs_value.operate(
+            &Operator::Equal,
+            rhs_value.as_ref(),
+            &values_equal,
+            program_memory,
+            stack_sizes,
+        )?;
+
+        //? Values only matter if present - flags_equal & (!present | values_equal)
+        BinaryNotInstruction::new_alloc(
+            program_memory,
+            &get_flag_of(self.get_address()),
+            destination.get_address(),
+            BOOLEAN_SIZE,
+        );
+        BinaryOrInstruction::new_alloc(
+            program_memory,
+            destination.get_address(),
+            values_equal.get_address(),
+            destination.get_address(),
+            BOOLEAN_SIZE,
+        );
+        BinaryAndInstruction::new_alloc(
+            program_memory,
+            flags_equal.get_address(),
+            destination.get_address(),
+            destination.get_address(),
+            BOOLEAN_SIZE,
+        );
+        Ok(())
+    }
+}
+
+/// Gets the address of the presence flag of an optional stored at `address`. A constant holds
+/// the flag and the value so only the flag's byte is kept
+fn get_flag_of(address: &Address) -> Address {
+    match address {
+        Address::Immediate(data) => Address::Immediate(Vec::from(&data[..BOOLEAN_SIZE])),
+        address => address.clone(),
+    }
+}
+
+impl Type for OptionalType {
+    fn get_type_symbol(&self) -> TypeSymbol {
+        TypeSymbol::optional_of(self.inner)
+    }
+
+    fn allocate_variable(
+        &mut self,
+        stack: &mut StackSizes,
+        _program_memory: &mut MemoryManager,
+    ) -> Result<(), String> {
+        if self.address.is_some() {
+            warn(
+                format!(
+                    "Allocating {:?} when it already has a memory address",
+                    self.get_type_symbol()
+                )
+                .as_str(),
+            )
+        }
+        self.address = Some(Address::StackDirect(
+            stack.increment_stack_size(self.get_length()),
+        ));
+
+        Ok(())
+    }
+
+    fn get_constant(&self, literal: &Literal) -> Result<Address, String> {
+        let mut constant = Vec::with_capacity(self.get_length());
+
+        match literal {
+            Literal::None => {
+                constant.push(BOOL_FALSE);
+                constant.extend(vec![0; self.inner_length]);
+            }
+            literal => {
+                let Address::Immediate(value) =
+                    TypeFactory::get_unallocated_type(&self.inner)?.get_constant(literal)?
+                else {
+                    panic!("Constant is not an immediate address")
+                };
+
+                constant.push(BOOL_TRUE);
+                constant.extend(value);
+            }
+        }
+
+        Ok(Address::Immediate(constant))
+    }
+
+    fn runtime_copy_from(
+        &self,
+        other: &dyn Type,
+        program_memory: &mut MemoryManager,
+    ) -> Result<CopyInstruction, String> {
+        //? Copy from an optional
+        if other.get_type_symbol() == self.get_type_symbol() {
+            return Ok(CopyInstruction::new_alloc(
+                program_memory,
+                other.get_address(),
+                self.address.as_ref().unwrap(),
+                self.get_length(),
+            ));
+        }
+
+        //? Copy from a value
+        let value = self.get_value_view()?;
+        value.runtime_copy_from(other, program_memory)?;
+
+        Ok(CopyInstruction::new_alloc(
+            program_memory,
+            &Address::Immediate(vec![BOOL_TRUE]),
+            self.get_flag_address(),
+            BOOLEAN_SIZE,
+        ))
+    }
+
+    fn runtime_copy_from_literal(
+        &self,
+        literal: &Literal,
+        program_memory: &mut MemoryManager,
+    ) -> Result<CopyInstruction, String> {
+        let constant = self.get_constant(literal)?;
+
+        Ok(CopyInstruction::new_alloc(
+            program_memory,
+            &constant,
+            self.address.as_ref().unwrap(),
+            self.get_length(),
+        ))
+    }
+
+    fn get_prefix_operation_result_type(&self, _operator: &Operator) -> Vec<TypeSymbol> {
+        Vec::new()
+    }
+
+    fn get_operation_result_type(&self, operator: &Operator, rhs: &TypeSymbol) -> Vec<TypeSymbol> {
+        match operator {
+            //? The values are compared with the inner type's equality
+            Operator::Equal | Operator::NotEqual
+                if *rhs == self.get_type_symbol()
+                    && TypeFactory::get_unallocated_type(&self.inner)
+                        .map(|inner| {
+                            inner
+                                .get_operation_result_type(&Operator::Equal, &self.inner)
+                                .contains(&TypeSymbol::Boolean)
+                        })
+                        .unwrap_or(false) =>
+            {
+                vec![TypeSymbol::Boolean]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn operate_prefix(
+        &self,
+        operator: &Operator,
+        _destination: &dyn Type,
+        _program_memory: &mut MemoryManager,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        Err(format!(
+            "Operator {} not supported on {} ({} it first)",
+            operator,
+            self.get_type_symbol(),
+            Operator::Unwrap
+        ))
+    }
+
+    fn operate(
+        &self,
+        operator: &Operator,
+        rhs: &dyn Type,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        if self
+            .get_operation_result_type(operator, &rhs.get_type_symbol())
+            .is_empty()
+        {
+            return Err(format!(
+                "Operator {} not supported between {} and {} ({} it first)",
+                operator,
+                self.get_type_symbol(),
+                rhs.get_type_symbol(),
+                Operator::Unwrap
+            ));
+        }
+
+        assert_eq!(destination.get_type_symbol(), TypeSymbol::Boolean);
+        self.equals(rhs, destination, program_memory, stack_sizes)?;
+
+        if *operator == Operator::NotEqual {
+            BinaryNotInstruction::new_alloc(
+                program_memory,
+                destination.get_address(),
+                destination.get_address(),
+                BOOLEAN_SIZE,
+            );
+        }
+        Ok(())
+    }
+
+    fn get_address(&self) -> &Address {
+        self.address.as_ref().unwrap()
+    }
+
+    fn get_length(&self) -> usize {
+        BOOLEAN_SIZE + self.inner_length
+    }
+
+    fn get_address_mut(&mut self) -> &mut Address {
+        self.address.as_mut().unwrap()
+    }
+
+    fn set_address(&mut self, address: Address) {
+        self.address = Some(address);
+    }
+
+    fn duplicate(&self) -> Box<dyn Type> {
+        bx!(OptionalType {
+            inner: self.inner,
+            inner_length: self.inner_length,
+            address: self.address.as_ref().cloned(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_optional(
+        inner: TypeSymbol,
+        stack_sizes: &mut StackSizes,
+        program_memory: &mut MemoryManager,
+    ) -> OptionalType {
+        let mut optional = OptionalType::new(inner).unwrap();
+        optional
+            .allocate_variable(stack_sizes, program_memory)
+            .unwrap();
+        optional
+    }
+
+    #[test]
+    fn compares_values_with_inner_equality() {
+        let mut program_memory = MemoryManager::new();
+        let mut stack_sizes = StackSizes::new();
+
+        for inner in [
+            TypeSymbol::Integer,
+            TypeSymbol::Float,
+            TypeSymbol::Character,
+        ] {
+            let lhs = get_optional(inner, &mut stack_sizes, &mut program_memory);
+            let rhs = get_optional(inner, &mut stack_sizes, &mut program_memory);
+            assert_eq!(
+                lhs.get_operation_result_type(&Operator::Equal, &lhs.get_type_symbol()),
+                vec![TypeSymbol::Boolean]
+            );
+
+            let mut destination = BoolType::new();
+            destination
+                .allocate_variable(&mut stack_sizes, &mut program_memory)
+                .unwrap();
+            for operator in [Operator::Equal, Operator::NotEqual] {
+                assert_eq!(
+                    lhs.operate(
+                        &operator,
+                        &rhs,
+                        &destination,
+                        &mut program_memory,
+                        &mut stack_sizes
+                    ),
+                    Ok(())
+                );
+            }
+
+            //? Comparing with a constant e.g. `o == none`
+            let mut none = OptionalType::new(inner).unwrap();
+            none.set_address(none.get_constant(&Literal::None).unwrap());
+            assert_eq!(
+                lhs.operate(
+                    &Operator::Equal,
+                    &none,
+                    &destination,
+                    &mut program_memory,
+                    &mut stack_sizes
+                ),
+                Ok(())
+            );
+            assert!(matches!(
+                get_flag_of(none.get_address()),
+                Address::Immediate(flag) if flag == vec![BOOL_FALSE]
+            ));
+        }
+    }
+}
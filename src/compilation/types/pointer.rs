@@ -6,8 +6,10 @@ use crate::processing::instructions::add_instruction_13::AddInstruction;
 use crate::processing::instructions::binary_not_7::BinaryNotInstruction;
 use crate::processing::instructions::copy_3::CopyInstruction;
 use crate::processing::instructions::equality_14::EqualityInstruction;
+use crate::processing::instructions::less_than_20::LessThanInstruction;
 use crate::processing::instructions::not_equal_15::NotEqualInstruction;
 use crate::processing::symbols::Literal;
+use crate::processing::types::boolean::BOOLEAN_SIZE;
 use crate::processing::types::{Operation, Type};
 use crate::util::{warn, USIZE_BYTES};
 use crate::{
@@ -18,7 +20,20 @@ use crate::{
 
 default_type_wrapper_struct_and_impl!(PointerWrapper, PointerType, TypeSymbol::Pointer);
 default_type_struct!(PointerType);
-default_type_initialiser!(PointerType, (Add, Subtract, Equal, NotEqual), ());
+default_type_initialiser!(
+    PointerType,
+    (
+        Add,
+        Subtract,
+        Equal,
+        NotEqual,
+        Less,
+        Greater,
+        LessEqual,
+        GreaterEqual
+    ),
+    ()
+);
 
 impl PointerType {
     pub fn duplicate_known(&self) -> PointerType {
@@ -121,6 +136,10 @@ impl Type for PointerType {
         self.address.as_mut().unwrap()
     }
 
+    fn set_address(&mut self, address: Address) {
+        self.address = Some(address);
+    }
+
     fn duplicate(&self) -> Box<dyn Type> {
         bx!(self.duplicate_known())
     }
@@ -297,3 +316,144 @@ impl Operation<PointerType> for NotEqual {
         Ok(())
     }
 }
+
+/// Compares two pointers as unsigned integers. `swap` compares `rhs < lhs` and `invert` negates the
+/// result, giving all four orderings from one instruction
+fn compare(
+    lhs: &PointerType,
+    rhs: &dyn Type,
+    destination: &dyn Type,
+    program_memory: &mut MemoryManager,
+    swap: bool,
+    invert: bool,
+) -> Result<(), String> {
+    assert_eq!(destination.get_type_symbol(), TypeSymbol::Boolean);
+    assert_eq!(rhs.get_type_symbol(), TypeSymbol::Pointer);
+
+    let (lhs, rhs) = if swap {
+        (rhs.get_address(), lhs.get_address())
+    } else {
+        (lhs.get_address(), rhs.get_address())
+    };
+    LessThanInstruction::new_alloc(
+        program_memory,
+        lhs,
+        rhs,
+        destination.get_address(),
+        USIZE_BYTES,
+        false,
+    );
+    if invert {
+        BinaryNotInstruction::new_alloc(
+            program_memory,
+            destination.get_address(),
+            destination.get_address(),
+            BOOLEAN_SIZE,
+        );
+    }
+    Ok(())
+}
+
+pub struct Less {}
+
+impl Operation<PointerType> for Less {
+    fn get_symbol(&self) -> Operator {
+        Operator::Less
+    }
+
+    fn get_result_type(&self, rhs: &TypeSymbol) -> Option<TypeSymbol> {
+        match rhs {
+            TypeSymbol::Pointer => Some(TypeSymbol::Boolean),
+            _ => None,
+        }
+    }
+
+    fn operate(
+        &self,
+        lhs: &PointerType,
+        rhs: &dyn Type,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        compare(lhs, rhs, destination, program_memory, false, false)
+    }
+}
+
+pub struct Greater {}
+
+impl Operation<PointerType> for Greater {
+    fn get_symbol(&self) -> Operator {
+        Operator::Greater
+    }
+
+    fn get_result_type(&self, rhs: &TypeSymbol) -> Option<TypeSymbol> {
+        match rhs {
+            TypeSymbol::Pointer => Some(TypeSymbol::Boolean),
+            _ => None,
+        }
+    }
+
+    fn operate(
+        &self,
+        lhs: &PointerType,
+        rhs: &dyn Type,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        compare(lhs, rhs, destination, program_memory, true, false)
+    }
+}
+
+pub struct LessEqual {}
+
+impl Operation<PointerType> for LessEqual {
+    fn get_symbol(&self) -> Operator {
+        Operator::LessEqual
+    }
+
+    fn get_result_type(&self, rhs: &TypeSymbol) -> Option<TypeSymbol> {
+        match rhs {
+            TypeSymbol::Pointer => Some(TypeSymbol::Boolean),
+            _ => None,
+        }
+    }
+
+    fn operate(
+        &self,
+        lhs: &PointerType,
+        rhs: &dyn Type,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        compare(lhs, rhs, destination, program_memory, true, true)
+    }
+}
+
+pub struct GreaterEqual {}
+
+impl Operation<PointerType> for GreaterEqual {
+    fn get_symbol(&self) -> Operator {
+        Operator::GreaterEqual
+    }
+
+    fn get_result_type(&self, rhs: &TypeSymbol) -> Option<TypeSymbol> {
+        match rhs {
+            TypeSymbol::Pointer => Some(TypeSymbol::Boolean),
+            _ => None,
+        }
+    }
+
+    fn operate(
+        &self,
+        lhs: &PointerType,
+        rhs: &dyn Type,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        compare(lhs, rhs, destination, program_memory, false, true)
+    }
+}
@@ -0,0 +1,189 @@
+use crate::address::Address;
+use crate::bx;
+use crate::memory::MemoryManager;
+use crate::processing::blocks::StackSizes;
+use crate::processing::instructions::copy_3::CopyInstruction;
+use crate::processing::symbols::{Literal, Operator, TypeSymbol};
+use crate::processing::types::{Type, TypeFactory};
+use crate::util::warn;
+
+/// A `(T, U, ...)`. Elements are stored one after another
+pub struct TupleType {
+    elements: &'static [TypeSymbol],
+    offsets: Vec<usize>,
+    length: usize,
+    address: Option<Address>,
+}
+
+impl TupleType {
+    pub fn new(elements: &'static [TypeSymbol]) -> Result<TupleType, String> {
+        if elements.len() < 2 {
+            return Err("Tuples must have at least two elements".to_string());
+        }
+
+        let mut offsets = Vec::with_capacity(elements.len());
+        let mut length = 0;
+        for element in elements {
+            offsets.push(length);
+            length += TypeFactory::get_unallocated_type(element)?.get_length();
+        }
+
+        Ok(TupleType {
+            elements,
+            offsets,
+            length,
+            address: None,
+        })
+    }
+
+    /// Creates a type referring to the element at `index`. Can be used as both a source and a
+    /// destination
+    pub fn get_element_view(&self, index: usize) -> Result<Box<dyn Type>, String> {
+        let Some(element) = self.elements.get(index) else {
+            return Err(format!(
+                "Index {} is out of range for {} ({} elements)",
+                index,
+                self.get_type_symbol(),
+                self.elements.len()
+            ));
+        };
+
+        let Some(element_address) = self.get_address().offset(self.offsets[index]) else {
+            return Err(format!(
+                "{} elements can only be accessed through a direct address",
+                self.get_type_symbol()
+            ));
+        };
+
+        let mut view = TypeFactory::get_unallocated_type(element)?;
+        view.set_address(element_address);
+        Ok(view)
+    }
+}
+
+impl Type for TupleType {
+    fn get_type_symbol(&self) -> TypeSymbol {
+        TypeSymbol::Tuple(self.elements)
+    }
+
+    fn allocate_variable(
+        &mut self,
+        stack: &mut StackSizes,
+        _program_memory: &mut MemoryManager,
+    ) -> Result<(), String> {
+        if self.address.is_some() {
+            warn(
+                format!(
+                    "Allocating {:?} when it already has a memory address",
+                    self.get_type_symbol()
+                )
+                .as_str(),
+            )
+        }
+        self.address = Some(Address::StackDirect(
+            stack.increment_stack_size(self.length),
+        ));
+
+        Ok(())
+    }
+
+    fn runtime_copy_from(
+        &self,
+        other: &dyn Type,
+        program_memory: &mut MemoryManager,
+    ) -> Result<CopyInstruction, String> {
+        if other.get_type_symbol() != self.get_type_symbol() {
+            return Err(format!(
+                "Copy not implemented from type '{}' to '{}'",
+                other.get_type_symbol(),
+                self.get_type_symbol()
+            ));
+        }
+
+        Ok(CopyInstruction::new_alloc(
+            program_memory,
+            other.get_address(),
+            self.address.as_ref().unwrap(),
+            self.length,
+        ))
+    }
+
+    fn runtime_copy_from_literal(
+        &self,
+        literal: &Literal,
+        _program_memory: &mut MemoryManager,
+    ) -> Result<CopyInstruction, String> {
+        Err(format!(
+            "{} cannot be assigned to {} - use a tuple e.g. (a, b)",
+            literal,
+            self.get_type_symbol()
+        ))
+    }
+
+    fn get_prefix_operation_result_type(&self, _operator: &Operator) -> Vec<TypeSymbol> {
+        Vec::new()
+    }
+
+    fn get_operation_result_type(
+        &self,
+        _operator: &Operator,
+        _rhs: &TypeSymbol,
+    ) -> Vec<TypeSymbol> {
+        Vec::new()
+    }
+
+    fn operate_prefix(
+        &self,
+        operator: &Operator,
+        _destination: &dyn Type,
+        _program_memory: &mut MemoryManager,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        Err(format!(
+            "Operator {} not supported on {}",
+            operator,
+            self.get_type_symbol()
+        ))
+    }
+
+    fn operate(
+        &self,
+        operator: &Operator,
+        rhs: &dyn Type,
+        _destination: &dyn Type,
+        _program_memory: &mut MemoryManager,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        Err(format!(
+            "Operator {} not supported between {} and {}",
+            operator,
+            self.get_type_symbol(),
+            rhs.get_type_symbol()
+        ))
+    }
+
+    fn get_address(&self) -> &Address {
+        self.address.as_ref().unwrap()
+    }
+
+    fn get_length(&self) -> usize {
+        self.length
+    }
+
+    fn get_address_mut(&mut self) -> &mut Address {
+        self.address.as_mut().unwrap()
+    }
+
+    fn set_address(&mut self, address: Address) {
+        self.address = Some(address);
+    }
+
+    fn duplicate(&self) -> Box<dyn Type> {
+        bx!(TupleType {
+            elements: self.elements,
+            offsets: self.offsets.clone(),
+            length: self.length,
+            address: self.address.as_ref().cloned(),
+        })
+    }
+}
@@ -0,0 +1,350 @@
+use crate::address::{Address, ADDRESS_CODE_BYTES, DIRECT_ADDRESS_BYTES};
+use crate::bx;
+use crate::errors::create_literal_not_impl_error;
+use crate::memory::MemoryManager;
+use crate::processing::blocks::StackSizes;
+use crate::processing::instructions::add_instruction_13::AddInstruction;
+use crate::processing::instructions::binary_not_7::BinaryNotInstruction;
+use crate::processing::instructions::copy_3::CopyInstruction;
+use crate::processing::instructions::equality_14::EqualityInstruction;
+use crate::processing::instructions::multiply_18::MultiplyInstruction;
+use crate::processing::instructions::not_equal_15::NotEqualInstruction;
+use crate::processing::symbols::{Literal, Operator, TypeSymbol};
+use crate::processing::types::pointer::PointerType;
+use crate::processing::types::{Type, TypeFactory};
+use crate::util::{read_usize, warn, USIZE_BYTES};
+
+/// A `ptr<T>`. Stores the serialised direct address of the target so it can be followed with an
+/// indirect address
+pub struct TypedPointerType {
+    target: TypeSymbol,
+    address: Option<Address>,
+}
+
+impl TypedPointerType {
+    pub fn new(target: TypeSymbol) -> TypedPointerType {
+        TypedPointerType {
+            target,
+            address: None,
+        }
+    }
+
+    fn get_target_size(&self) -> Result<usize, String> {
+        Ok(TypeFactory::get_unallocated_type(&self.target)?.get_length())
+    }
+
+    /// Creates a type referring to the value pointed to. Can be used as both a source and a
+    /// destination
+    pub fn dereference(
+        &self,
+        program_memory: &mut MemoryManager,
+        stack_sizes: &mut StackSizes,
+    ) -> Result<Box<dyn Type>, String> {
+        let target_address = match self.get_address().to_indirect() {
+            Some(target_address) => target_address,
+            //? Only direct addresses can be followed so a pointer that isn't stored directly e.g.
+            //? one that was itself dereferenced is copied to the stack first
+            None => {
+                let mut pointer = TypedPointerType::new(self.target);
+                pointer.allocate_variable(stack_sizes, program_memory)?;
+                pointer.runtime_copy_from(self, program_memory)?;
+                pointer.get_address().to_indirect().unwrap()
+            }
+        };
+
+        let mut target = TypeFactory::get_unallocated_type(&self.target)?;
+        target.set_address(target_address);
+        Ok(target)
+    }
+
+    /// Puts `self` offset by `count` elements (`count` is a `ptr`) into `destination`
+    fn offset_into(
+        &self,
+        count: &dyn Type,
+        negate: bool,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        let scale = self.get_target_size()?;
+
+        CopyInstruction::new_alloc(
+            program_memory,
+            self.get_address(),
+            destination.get_address(),
+            DIRECT_ADDRESS_BYTES,
+        );
+
+        //? Only offset the address, not the address code
+        let Some(destination_offset) = destination.get_address().offset(ADDRESS_CODE_BYTES) else {
+            return Err("Pointer arithmetic results must be stored directly".to_string());
+        };
+
+        //? Scale at compile-time if possible
+        if let Address::Immediate(data) = count.get_address() {
+            let mut offset = read_usize(data).wrapping_mul(scale);
+            if negate {
+                offset = offset.wrapping_neg();
+            }
+
+            AddInstruction::new_alloc(
+                program_memory,
+                &destination_offset,
+                &Address::Immediate(Vec::from(offset.to_le_bytes())),
+                &destination_offset,
+                USIZE_BYTES,
+            );
+            return Ok(());
+        }
+
+        let mut step = PointerType::new();
+        step.allocate_variable(stack_sizes, program_memory)?;
+        if negate {
+            //? Two's complement of the count
+            BinaryNotInstruction::new_alloc(
+                program_memory,
+                count.get_address(),
+                step.get_address(),
+                USIZE_BYTES,
+            );
+            AddInstruction::new_alloc(
+                program_memory,
+                step.get_address(),
+                &Address::Immediate(Vec::from(1usize.to_le_bytes())),
+                step.get_address(),
+                USIZE_BYTES,
+            );
+        } else {
+            CopyInstruction::new_alloc(
+                program_memory,
+                count.get_address(),
+                step.get_address(),
+                USIZE_BYTES,
+            );
+        }
+
+        //? Scale the count by the target's size
+        MultiplyInstruction::new_alloc(
+            program_memory,
+            step.get_address(),
+            &Address::Immediate(Vec::from(scale.to_le_bytes())),
+            step.get_address(),
+            USIZE_BYTES,
+        );
+        AddInstruction::new_alloc(
+            program_memory,
+            &destination_offset,
+            step.get_address(),
+            &destination_offset,
+            USIZE_BYTES,
+        );
+
+        Ok(())
+    }
+}
+
+impl Type for TypedPointerType {
+    fn get_type_symbol(&self) -> TypeSymbol {
+        TypeSymbol::pointer_to(self.target)
+    }
+
+    fn allocate_variable(
+        &mut self,
+        stack: &mut StackSizes,
+        _program_memory: &mut MemoryManager,
+    ) -> Result<(), String> {
+        if self.address.is_some() {
+            warn(
+                format!(
+                    "Allocating {} when it already has a memory address",
+                    self.get_type_symbol()
+                )
+                .as_str(),
+            )
+        }
+        self.address = Some(Address::StackDirect(
+            stack.increment_stack_size(DIRECT_ADDRESS_BYTES),
+        ));
+
+        Ok(())
+    }
+
+    fn get_constant(&self, literal: &Literal) -> Result<Address, String> {
+        create_literal_not_impl_error(literal, self.get_type_symbol())
+    }
+
+    fn runtime_copy_from(
+        &self,
+        other: &dyn Type,
+        program_memory: &mut MemoryManager,
+    ) -> Result<CopyInstruction, String> {
+        if other.get_type_symbol() != self.get_type_symbol() {
+            return Err(format!(
+                "Copy not implemented from type '{}' to '{}'",
+                other.get_type_symbol(),
+                self.get_type_symbol()
+            ));
+        }
+
+        Ok(CopyInstruction::new_alloc(
+            program_memory,
+            other.get_address(),
+            self.address.as_ref().unwrap(),
+            DIRECT_ADDRESS_BYTES,
+        ))
+    }
+
+    fn runtime_copy_from_literal(
+        &self,
+        literal: &Literal,
+        program_memory: &mut MemoryManager,
+    ) -> Result<CopyInstruction, String> {
+        let constant = self.get_constant(literal)?;
+
+        Ok(CopyInstruction::new_alloc(
+            program_memory,
+            &constant,
+            self.address.as_ref().unwrap(),
+            DIRECT_ADDRESS_BYTES,
+        ))
+    }
+
+    fn get_prefix_operation_result_type(&self, _operator: &Operator) -> Vec<TypeSymbol> {
+        Vec::new()
+    }
+
+    fn get_operation_result_type(&self, operator: &Operator, rhs: &TypeSymbol) -> Vec<TypeSymbol> {
+        match (operator, rhs) {
+            (Operator::Add | Operator::Subtract, TypeSymbol::Pointer) => {
+                vec![self.get_type_symbol()]
+            }
+            (Operator::Equal | Operator::NotEqual, rhs) if *rhs == self.get_type_symbol() => {
+                vec![TypeSymbol::Boolean]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn operate_prefix(
+        &self,
+        operator: &Operator,
+        _destination: &dyn Type,
+        _program_memory: &mut MemoryManager,
+        _stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        Err(format!(
+            "Operator {} not supported on {}",
+            operator,
+            self.get_type_symbol()
+        ))
+    }
+
+    fn operate(
+        &self,
+        operator: &Operator,
+        rhs: &dyn Type,
+        destination: &dyn Type,
+        program_memory: &mut MemoryManager,
+        stack_sizes: &mut StackSizes,
+    ) -> Result<(), String> {
+        if self
+            .get_operation_result_type(operator, &rhs.get_type_symbol())
+            .is_empty()
+        {
+            return Err(format!(
+                "Operator {} not supported between {} and {}",
+                operator,
+                self.get_type_symbol(),
+                rhs.get_type_symbol()
+            ));
+        }
+
+        match operator {
+            Operator::Add | Operator::Subtract => {
+                assert_eq!(destination.get_type_symbol(), self.get_type_symbol());
+                self.offset_into(
+                    rhs,
+                    *operator == Operator::Subtract,
+                    destination,
+                    program_memory,
+                    stack_sizes,
+                )
+            }
+            Operator::Equal => {
+                assert_eq!(destination.get_type_symbol(), TypeSymbol::Boolean);
+                EqualityInstruction::new_alloc(
+                    program_memory,
+                    self.get_address(),
+                    rhs.get_address(),
+                    destination.get_address(),
+                    DIRECT_ADDRESS_BYTES,
+                );
+                Ok(())
+            }
+            Operator::NotEqual => {
+                assert_eq!(destination.get_type_symbol(), TypeSymbol::Boolean);
+                NotEqualInstruction::new_alloc(
+                    program_memory,
+                    self.get_address(),
+                    rhs.get_address(),
+                    destination.get_address(),
+                    DIRECT_ADDRESS_BYTES,
+                );
+                Ok(())
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn get_address(&self) -> &Address {
+        self.address.as_ref().unwrap()
+    }
+
+    fn get_length(&self) -> usize {
+        DIRECT_ADDRESS_BYTES
+    }
+
+    fn get_address_mut(&mut self) -> &mut Address {
+        self.address.as_mut().unwrap()
+    }
+
+    fn set_address(&mut self, address: Address) {
+        self.address = Some(address);
+    }
+
+    fn duplicate(&self) -> Box<dyn Type> {
+        let mut t = TypedPointerType::new(self.target);
+        t.address = self.address.as_ref().cloned();
+        bx!(t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dereferences_chained_pointers() {
+        let mut program_memory = MemoryManager::new();
+        let mut stack_sizes = StackSizes::new();
+
+        let mut outer = TypedPointerType::new(TypeSymbol::pointer_to(TypeSymbol::Integer));
+        outer
+            .allocate_variable(&mut stack_sizes, &mut program_memory)
+            .unwrap();
+        let inner = outer
+            .dereference(&mut program_memory, &mut stack_sizes)
+            .unwrap();
+        assert!(inner.get_address().to_indirect().is_none());
+
+        //? Following the inner pointer first copies it somewhere direct
+        let mut inner_pointer = TypedPointerType::new(TypeSymbol::Integer);
+        inner_pointer.set_address(inner.get_address().clone());
+        let start = program_memory.position();
+        let value = inner_pointer
+            .dereference(&mut program_memory, &mut stack_sizes)
+            .unwrap();
+        assert_eq!(value.get_type_symbol(), TypeSymbol::Integer);
+        assert!(program_memory.position() > start);
+    }
+}
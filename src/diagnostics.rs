@@ -0,0 +1,603 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::IsTerminal;
+
+use crate::lexical_parsing::span::Span;
+
+pub const INDENTATION_ERROR: &str = "E0001";
+pub const SYMBOL_ERROR: &str = "E0002";
+pub const IMPORT_ERROR: &str = "E0003";
+pub const COMPILE_ERROR: &str = "E0004";
+pub const MODULE_ERROR: &str = "E0005";
+
+pub const UNUSED_VARIABLE_WARNING: &str = "W0001";
+pub const UNREACHABLE_CODE_WARNING: &str = "W0002";
+pub const ELIF_AFTER_ELSE_WARNING: &str = "W0003";
+pub const SHADOWED_NAME_WARNING: &str = "W0004";
+pub const CONSTANT_CONDITION_WARNING: &str = "W0005";
+
+/// The number of errors collected before giving up if no other limit is given
+pub const DEFAULT_ERROR_LIMIT: usize = 50;
+/// Command line flag setting the error limit e.g. `--error-limit 10`
+pub const ERROR_LIMIT_FLAG: &str = "--error-limit";
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[33m";
+const BLUE: &str = "\x1b[34m";
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// How diagnostics are written out
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum OutputFormat {
+    /// Rendered with source snippets for people to read
+    Human,
+    /// One JSON object per line for tools to parse (see `Diagnostic::to_json`)
+    JsonLines,
+}
+
+impl Severity {
+    fn get_name(&self) -> &str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+
+    fn get_colour(&self) -> &str {
+        match self {
+            Severity::Error => RED,
+            Severity::Warning => YELLOW,
+        }
+    }
+}
+
+/// A position in the source code with an optional message explaining its relevance
+#[derive(Clone, Debug)]
+pub struct Label {
+    pub file_name: String,
+    pub line_index: usize,
+    /// `None` if the label refers to the whole line
+    pub span: Option<Span>,
+    pub message: String,
+}
+
+impl Label {
+    pub fn new(file_name: String, line_index: usize, span: Option<Span>, message: String) -> Label {
+        Label {
+            file_name,
+            line_index,
+            span,
+            message,
+        }
+    }
+
+    fn get_location(&self) -> String {
+        match &self.span {
+            Some(span) => format!(
+                "{}:{}:{}",
+                self.file_name,
+                self.line_index + 1,
+                span.start + 1
+            ),
+            None => format!("{}:{}", self.file_name, self.line_index + 1),
+        }
+    }
+}
+
+/// Provides the source code referred to by diagnostics
+pub trait SourceProvider {
+    fn get_source_line(&self, file_name: &str, line_index: usize) -> Option<&str>;
+}
+
+/// An error or warning about the source code
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: Option<&'static str>,
+    pub message: String,
+    /// Where the problem is
+    pub primary: Option<Label>,
+    /// Other relevant positions e.g. a previous definition
+    pub secondary: Vec<Label>,
+    pub notes: Vec<String>,
+    pub help: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, message: String) -> Diagnostic {
+        Diagnostic {
+            severity,
+            code: None,
+            message,
+            primary: None,
+            secondary: Vec::new(),
+            notes: Vec::new(),
+            help: Vec::new(),
+        }
+    }
+
+    pub fn error(message: String) -> Diagnostic {
+        Diagnostic::new(Severity::Error, message)
+    }
+
+    pub fn warning(message: String) -> Diagnostic {
+        Diagnostic::new(Severity::Warning, message)
+    }
+
+    pub fn with_code(mut self, code: &'static str) -> Diagnostic {
+        self.code = Some(code);
+        self
+    }
+
+    pub fn with_primary(mut self, label: Label) -> Diagnostic {
+        self.primary = Some(label);
+        self
+    }
+
+    pub fn with_secondary(mut self, label: Label) -> Diagnostic {
+        self.secondary.push(label);
+        self
+    }
+
+    pub fn with_note(mut self, note: String) -> Diagnostic {
+        self.notes.push(note);
+        self
+    }
+
+    pub fn with_help(mut self, help: String) -> Diagnostic {
+        self.help.push(help);
+        self
+    }
+
+    /// Renders the diagnostic with the source lines it refers to, underlining the labelled spans.
+    /// Labels whose source isn't available are shown as locations only
+    pub fn render(&self, sources: &dyn SourceProvider, colour: bool) -> String {
+        let paint = |style: &str, text: &str| {
+            if colour {
+                format!("{style}{text}{RESET}")
+            } else {
+                text.to_string()
+            }
+        };
+        let severity_style = format!("{}{}", BOLD, self.severity.get_colour());
+        let gutter_style = format!("{BOLD}{BLUE}");
+
+        let mut output = self.render_header(colour);
+
+        let labels: Vec<(&Label, bool)> = self
+            .primary
+            .iter()
+            .map(|l| (l, true))
+            .chain(self.secondary.iter().map(|l| (l, false)))
+            .collect();
+        let gutter_width = labels
+            .iter()
+            .map(|(l, _)| (l.line_index + 1).to_string().len())
+            .max()
+            .unwrap_or(0);
+        let gutter = " ".repeat(gutter_width);
+
+        //? Labels on the same line are shown under a single copy of the line
+        let mut shown_lines: Vec<(&str, usize)> = Vec::new();
+        for (label, _) in &labels {
+            if shown_lines.contains(&(label.file_name.as_str(), label.line_index)) {
+                continue;
+            }
+            shown_lines.push((label.file_name.as_str(), label.line_index));
+
+            let arrow = if shown_lines.len() == 1 { "-->" } else { ":::" };
+            output += &format!(
+                "\n{}{} {}",
+                gutter,
+                paint(&gutter_style, arrow),
+                label.get_location()
+            );
+
+            let Some(source) = sources.get_source_line(&label.file_name, label.line_index) else {
+                continue;
+            };
+            output += &format!("\n{} {}", gutter, paint(&gutter_style, "|"));
+            output += &format!(
+                "\n{} {}",
+                paint(
+                    &gutter_style,
+                    &format!("{:>gutter_width$} |", label.line_index + 1)
+                ),
+                source
+            );
+
+            for (line_label, primary) in &labels {
+                if line_label.file_name != label.file_name
+                    || line_label.line_index != label.line_index
+                {
+                    continue;
+                }
+
+                let span = line_label
+                    .span
+                    .unwrap_or(Span::new(0, source.chars().count()));
+                //? Copy tabs from the source so the underline lines up
+                let padding: String = source
+                    .chars()
+                    .chain(std::iter::repeat(' '))
+                    .take(span.start)
+                    .map(|c| if c == '\t' { '\t' } else { ' ' })
+                    .collect();
+                let (marker, style) = if *primary {
+                    ("^", severity_style.as_str())
+                } else {
+                    ("-", gutter_style.as_str())
+                };
+                let mut underline = marker.repeat((span.end - span.start).max(1));
+                if !line_label.message.is_empty() {
+                    underline += &format!(" {}", line_label.message);
+                }
+
+                output += &format!(
+                    "\n{} {} {}{}",
+                    gutter,
+                    paint(&gutter_style, "|"),
+                    padding,
+                    paint(style, &underline)
+                );
+            }
+        }
+
+        if !labels.is_empty() && (!self.notes.is_empty() || !self.help.is_empty()) {
+            output += &format!("\n{} {}", gutter, paint(&gutter_style, "|"));
+        }
+        let notes = self.notes.iter().map(|n| ("note:", n));
+        for (kind, text) in notes.chain(self.help.iter().map(|h| ("help:", h))) {
+            output += &format!(
+                "\n{} {} {} {}",
+                gutter,
+                paint(&gutter_style, "="),
+                paint(BOLD, kind),
+                text
+            );
+        }
+
+        output
+    }
+
+    fn render_header(&self, colour: bool) -> String {
+        let title = match self.code {
+            Some(code) => format!("{}[{}]", self.severity.get_name(), code),
+            None => self.severity.get_name().to_string(),
+        };
+
+        if colour {
+            let severity_colour = self.severity.get_colour();
+            format!(
+                "{BOLD}{severity_colour}{title}{RESET}{BOLD}: {}{RESET}",
+                self.message
+            )
+        } else {
+            format!("{}: {}", title, self.message)
+        }
+    }
+}
+
+/// Shows the diagnostic without source lines
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render_header(false))?;
+        if let Some(primary) = &self.primary {
+            f.write_str(&format!("\n --> {}", primary.get_location()))?;
+        }
+        for note in &self.notes {
+            f.write_str(&format!("\n = note: {}", note))?;
+        }
+        for help in &self.help {
+            f.write_str(&format!("\n = help: {}", help))?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for Diagnostic {}
+
+/// Escapes a string and wraps it in quotes for use in JSON
+fn json_string(string: &str) -> String {
+    let mut escaped = String::from('"');
+    for c in string.chars() {
+        match c {
+            '"' => escaped += "\\\"",
+            '\\' => escaped += "\\\\",
+            '\n' => escaped += "\\n",
+            '\r' => escaped += "\\r",
+            '\t' => escaped += "\\t",
+            c if (c as u32) < 0x20 => escaped += &format!("\\u{:04x}", c as u32),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+impl Diagnostic {
+    /// Converts the diagnostic to a single line JSON object. The schema is stable - fields may be
+    /// added but existing ones won't be removed or change meaning:
+    /// * `file` - the file name, or `null` if the diagnostic has no location
+    /// * `line` - the 1-based line number, or `null`
+    /// * `column` - the 1-based column where the primary span starts, or `null` if the diagnostic
+    ///   refers to a whole line
+    /// * `end_column` - the 1-based column after the end of the primary span, or `null`
+    /// * `severity` - `"error"` or `"warning"`
+    /// * `code` - e.g. `"E0002"`, or `null`
+    /// * `message` - the diagnostic's message
+    pub fn to_json(&self) -> String {
+        let null = || "null".to_string();
+        let (file, line, column, end_column) = match &self.primary {
+            Some(label) => (
+                json_string(&label.file_name),
+                (label.line_index + 1).to_string(),
+                label
+                    .span
+                    .map_or_else(null, |span| (span.start + 1).to_string()),
+                label
+                    .span
+                    .map_or_else(null, |span| (span.end + 1).to_string()),
+            ),
+            None => (null(), null(), null(), null()),
+        };
+
+        format!(
+            "{{\"file\":{},\"line\":{},\"column\":{},\"end_column\":{},\"severity\":{},\"code\":{},\"message\":{}}}",
+            file,
+            line,
+            column,
+            end_column,
+            json_string(self.severity.get_name()),
+            self.code.map_or_else(null, json_string),
+            json_string(&self.message)
+        )
+    }
+}
+
+/// Command line flag choosing how diagnostics are written e.g. `--format json`
+pub const OUTPUT_FORMAT_FLAG: &str = "--format";
+
+/// Returns the output format given with `OUTPUT_FORMAT_FLAG` in `args`, or
+/// `OutputFormat::Human`
+pub fn get_output_format(args: &[String]) -> Result<OutputFormat, String> {
+    let Some(position) = args.iter().position(|a| a == OUTPUT_FORMAT_FLAG) else {
+        return Ok(OutputFormat::Human);
+    };
+    match args.get(position + 1).map(|format| format.as_str()) {
+        Some("human") => Ok(OutputFormat::Human),
+        Some("json") => Ok(OutputFormat::JsonLines),
+        _ => Err(format!(
+            "{} must be followed by 'human' or 'json'",
+            OUTPUT_FORMAT_FLAG
+        )),
+    }
+}
+
+/// Returns the error limit given with `ERROR_LIMIT_FLAG` in `args`, or `DEFAULT_ERROR_LIMIT`
+pub fn get_error_limit(args: &[String]) -> Result<usize, String> {
+    let Some(position) = args.iter().position(|a| a == ERROR_LIMIT_FLAG) else {
+        return Ok(DEFAULT_ERROR_LIMIT);
+    };
+    match args.get(position + 1).map(|limit| limit.parse::<usize>()) {
+        Some(Ok(limit)) if limit > 0 => Ok(limit),
+        _ => Err(format!(
+            "{} must be followed by a number of errors greater than 0",
+            ERROR_LIMIT_FLAG
+        )),
+    }
+}
+
+/// Collects diagnostics so that every independent error can be reported in a single run, stopping
+/// once `error_limit` errors have been collected
+pub struct DiagnosticCollector {
+    diagnostics: Vec<Diagnostic>,
+    error_limit: usize,
+    error_count: usize,
+}
+
+impl DiagnosticCollector {
+    pub fn new(error_limit: usize) -> DiagnosticCollector {
+        DiagnosticCollector {
+            diagnostics: Vec::new(),
+            error_limit,
+            error_count: 0,
+        }
+    }
+
+    pub fn push(&mut self, diagnostic: Diagnostic) {
+        if diagnostic.severity == Severity::Error {
+            self.error_count += 1;
+        }
+        self.diagnostics.push(diagnostic);
+    }
+
+    #[cfg(test)]
+    pub fn get_diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.error_count > 0
+    }
+
+    /// Returns true if the error limit has been reached and processing should stop
+    pub fn is_full(&self) -> bool {
+        self.error_count >= self.error_limit
+    }
+
+    /// Renders every diagnostic followed by a summary of how many errors were found
+    pub fn render(&self, sources: &dyn SourceProvider, colour: bool) -> String {
+        let mut output = String::new();
+        for diagnostic in &self.diagnostics {
+            output += &diagnostic.render(sources, colour);
+            output += "\n\n";
+        }
+
+        if self.has_errors() {
+            let mut summary = if self.error_count == 1 {
+                Diagnostic::error("Aborting due to previous error".to_string())
+            } else {
+                Diagnostic::error(format!(
+                    "Aborting due to {} previous errors",
+                    self.error_count
+                ))
+            };
+            if self.is_full() {
+                summary = summary.with_note(format!(
+                    "Stopped after reaching the limit of {} errors",
+                    self.error_limit
+                ));
+            }
+            output += &summary.render(sources, colour);
+        }
+
+        output
+    }
+
+    /// Renders every diagnostic, using colour if stdout is a terminal
+    pub fn render_for_stdout(&self, sources: &dyn SourceProvider) -> String {
+        self.render(sources, std::io::stdout().is_terminal())
+    }
+
+    /// Writes every diagnostic as one JSON object per line (see `Diagnostic::to_json`). No summary
+    /// is included
+    pub fn render_json_lines(&self) -> String {
+        let mut output = String::new();
+        for diagnostic in &self.diagnostics {
+            output += &diagnostic.to_json();
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Renders every diagnostic in `format`, using colour for `OutputFormat::Human` if stdout is a
+    /// terminal
+    pub fn render_as(&self, format: OutputFormat, sources: &dyn SourceProvider) -> String {
+        match format {
+            OutputFormat::Human => self.render_for_stdout(sources),
+            OutputFormat::JsonLines => self.render_json_lines(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexical_parsing::preprocessor::{convert_to_symbols, SymbolData};
+
+    const FIXTURE_SOURCE: &str = "tests/fixtures/diagnostics.why";
+    const FIXTURE_JSON_LINES: &str = "tests/fixtures/diagnostics.jsonl";
+
+    #[test]
+    fn json_lines_match_fixture() {
+        let mut symbol_data = SymbolData::new();
+        let mut diagnostics = DiagnosticCollector::new(DEFAULT_ERROR_LIMIT);
+        convert_to_symbols(
+            FIXTURE_SOURCE.to_string(),
+            &mut symbol_data,
+            &mut diagnostics,
+        )
+        .unwrap();
+
+        let expected = std::fs::read_to_string(FIXTURE_JSON_LINES).unwrap();
+        assert_eq!(diagnostics.render_json_lines(), expected);
+    }
+
+    struct TestSources(Vec<(&'static str, usize, &'static str)>);
+
+    impl SourceProvider for TestSources {
+        fn get_source_line(&self, file_name: &str, line_index: usize) -> Option<&str> {
+            self.0
+                .iter()
+                .find(|(f, l, _)| *f == file_name && *l == line_index)
+                .map(|(_, _, source)| *source)
+        }
+    }
+
+    fn label(file_name: &str, line_index: usize, span: Option<Span>, message: &str) -> Label {
+        Label::new(file_name.to_string(), line_index, span, message.to_string())
+    }
+
+    #[test]
+    fn renders_multiple_labels() {
+        let sources = TestSources(vec![
+            ("main.why", 1, "\tint a = true"),
+            ("lib.why", 9, "fn f()"),
+        ]);
+        let diagnostic = Diagnostic::error("Mismatched types".to_string())
+            .with_code("E0001")
+            .with_primary(label("main.why", 1, Some(Span::new(9, 13)), "expected int"))
+            .with_secondary(label("main.why", 1, Some(Span::new(1, 4)), "declared here"))
+            .with_secondary(label("lib.why", 9, None, "called here"))
+            .with_secondary(label("missing.why", 0, None, ""));
+
+        let expected = [
+            "error[E0001]: Mismatched types",
+            "  --> main.why:2:10",
+            "   |",
+            " 2 | \tint a = true",
+            "   | \t        ^^^^ expected int",
+            "   | \t--- declared here",
+            "  ::: lib.why:10",
+            "   |",
+            "10 | fn f()",
+            "   | ------ called here",
+            "  ::: missing.why:1",
+        ]
+        .join("\n");
+        assert_eq!(diagnostic.render(&sources, false), expected);
+    }
+
+    #[test]
+    fn renders_notes_and_help() {
+        let sources = TestSources(vec![("main.why", 0, "int a = 5")]);
+        let diagnostic = Diagnostic::warning("Unused variable 'a'".to_string())
+            .with_primary(label("main.why", 0, Some(Span::new(4, 5)), ""))
+            .with_note("'a' is never read".to_string())
+            .with_help("Prefix the name with '_' to allow this".to_string());
+
+        let expected = [
+            "warning: Unused variable 'a'",
+            " --> main.why:1:5",
+            "  |",
+            "1 | int a = 5",
+            "  |     ^",
+            "  |",
+            "  = note: 'a' is never read",
+            "  = help: Prefix the name with '_' to allow this",
+        ]
+        .join("\n");
+        assert_eq!(diagnostic.render(&sources, false), expected);
+
+        //? Notes and help are shown without a snippet if there are no labels
+        let diagnostic = Diagnostic::error("Aborting".to_string()).with_note("Stopped".to_string());
+        assert_eq!(
+            diagnostic.render(&sources, false),
+            "error: Aborting\n = note: Stopped"
+        );
+    }
+
+    #[test]
+    fn colours_only_when_asked() {
+        let sources = TestSources(vec![("main.why", 0, "int a = 5")]);
+        let diagnostic = Diagnostic::error("Bad".to_string())
+            .with_primary(label("main.why", 0, Some(Span::new(0, 3)), "here"))
+            .with_help("Fix it".to_string());
+
+        let plain = diagnostic.render(&sources, false);
+        assert!(!plain.contains('\x1b'));
+
+        let coloured = diagnostic.render(&sources, true);
+        assert!(coloured.starts_with(&format!("{BOLD}{RED}error{RESET}{BOLD}: Bad{RESET}")));
+        assert!(coloured.contains(&format!("{BOLD}{RED}^^^ here{RESET}")));
+        assert!(coloured.contains(&format!("{BOLD}help:{RESET} Fix it")));
+    }
+}
@@ -1,7 +1,14 @@
+use std::env;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::fs;
 use std::io::Error as IoError;
+use std::path::{Path, PathBuf};
+
+/// Environment variable listing directories to search for imported files, separated as in `PATH`
+pub const SEARCH_PATH_ENV_VAR: &str = "WHY_PATH";
+/// Command line flag adding a directory to search for imported files e.g. `-I lib` or `-Ilib`
+pub const SEARCH_PATH_FLAG: &str = "-I";
 
 #[derive(Debug)]
 pub struct FileReadError {
@@ -32,4 +39,70 @@ pub fn load_file(input_file: &str) -> Result<String, FileReadError> {
         }
         Ok(value) => Ok(value),
     }
-}
\ No newline at end of file
+}
+
+/// Returns the absolute path of a file with symlinks resolved, or the path as given if the file
+/// can't be found
+pub fn get_canonical_path(file_name: &str) -> PathBuf {
+    fs::canonicalize(file_name).unwrap_or_else(|_| PathBuf::from(file_name))
+}
+
+/// Returns the directories to search for imported files - those given with `SEARCH_PATH_FLAG` in
+/// `args`, in order, followed by those in `SEARCH_PATH_ENV_VAR`
+pub fn get_search_paths(args: &[String]) -> Result<Vec<PathBuf>, String> {
+    let mut search_paths = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let Some(path) = arg.strip_prefix(SEARCH_PATH_FLAG) else {
+            continue;
+        };
+        if !path.is_empty() {
+            search_paths.push(PathBuf::from(path));
+            continue;
+        }
+        match args.next() {
+            Some(path) => search_paths.push(PathBuf::from(path)),
+            None => {
+                return Err(format!(
+                    "{} must be followed by a directory",
+                    SEARCH_PATH_FLAG
+                ))
+            }
+        }
+    }
+
+    if let Some(paths) = env::var_os(SEARCH_PATH_ENV_VAR) {
+        search_paths.extend(env::split_paths(&paths).filter(|p| !p.as_os_str().is_empty()));
+    }
+
+    Ok(search_paths)
+}
+
+/// Finds an imported file, looking relative to the directory of the importing file first and then
+/// in each of `search_paths`
+pub fn find_import(
+    importing_file: &str,
+    import_path: &Path,
+    search_paths: &[PathBuf],
+) -> Result<PathBuf, String> {
+    let importing_directory = Path::new(importing_file).parent().unwrap_or(Path::new(""));
+
+    let candidates: Vec<PathBuf> = std::iter::once(importing_directory)
+        .chain(search_paths.iter().map(|p| p.as_path()))
+        .map(|directory| directory.join(import_path))
+        .collect();
+
+    match candidates.iter().find(|c| c.is_file()) {
+        Some(found) => Ok(found.clone()),
+        None => Err(format!(
+            "File '{}' not found, searched: {}",
+            import_path.display(),
+            candidates
+                .iter()
+                .map(|c| format!("'{}'", c.display()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
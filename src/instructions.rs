@@ -1,13 +1,48 @@
 use crate::instructions::example_instruction::ExampleInstruction;
+use crate::memory::runtime_memory::RuntimeMemory;
 
 mod example_instruction;
+pub mod panic_if_not_17;
+pub mod multiply_18;
+pub mod divide_19;
+pub mod less_than_20;
+pub mod extend_21;
+pub mod float_operation_22;
+pub mod float_convert_23;
+pub mod print_24;
+pub mod bitwise_25;
+pub mod shift_26;
+pub mod modulo_27;
+
+use panic_if_not_17::PanicIfNotInstruction;
+use multiply_18::MultiplyInstruction;
+use divide_19::DivideInstruction;
+use less_than_20::LessThanInstruction;
+use extend_21::ExtendInstruction;
+use float_operation_22::FloatOperationInstruction;
+use float_convert_23::FloatConvertInstruction;
+use print_24::PrintInstruction;
+use bitwise_25::BitwiseInstruction;
+use shift_26::ShiftInstruction;
+use modulo_27::ModuloInstruction;
 
 macro_rules! instruction {
-    ($code: expr, $instruction: ident) => {
-        if $code == $instruction::CODE { $instruction::execute(); return; }
+    ($code: expr, $runtime_memory: expr, $instruction: ident) => {
+        if $code == $instruction::CODE { $instruction::execute($runtime_memory); return; }
     };
 }
 
-pub fn execute_code(code: u8) {
-    instruction!(code, ExampleInstruction);
-}
\ No newline at end of file
+pub fn execute_code(code: u8, runtime_memory: &mut RuntimeMemory) {
+    instruction!(code, runtime_memory, ExampleInstruction);
+    instruction!(code, runtime_memory, PanicIfNotInstruction);
+    instruction!(code, runtime_memory, MultiplyInstruction);
+    instruction!(code, runtime_memory, DivideInstruction);
+    instruction!(code, runtime_memory, LessThanInstruction);
+    instruction!(code, runtime_memory, ExtendInstruction);
+    instruction!(code, runtime_memory, FloatOperationInstruction);
+    instruction!(code, runtime_memory, FloatConvertInstruction);
+    instruction!(code, runtime_memory, PrintInstruction);
+    instruction!(code, runtime_memory, BitwiseInstruction);
+    instruction!(code, runtime_memory, ShiftInstruction);
+    instruction!(code, runtime_memory, ModuloInstruction);
+}
@@ -0,0 +1,65 @@
+use crate::memory::address::Address;
+use crate::memory::MemoryManager;
+use crate::memory::runtime_memory::RuntimeMemory;
+use crate::util::USIZE_BYTES;
+
+/// A bytewise operation between two values of the same length
+#[derive(Copy, Clone)]
+pub enum BitwiseOperation {
+    And = 0,
+    Or = 1,
+    Xor = 2,
+}
+
+impl BitwiseOperation {
+    fn from_byte(byte: u8) -> BitwiseOperation {
+        match byte {
+            0 => BitwiseOperation::And,
+            1 => BitwiseOperation::Or,
+            2 => BitwiseOperation::Xor,
+            _ => panic!("Invalid bitwise operation '{}'", byte),
+        }
+    }
+}
+
+pub struct BitwiseInstruction {
+    address: usize,
+}
+
+impl BitwiseInstruction {
+    pub const CODE: u8 = 25;
+
+    pub fn new_alloc(program_memory: &mut MemoryManager, operation: BitwiseOperation, lhs: &Address, rhs: &Address, destination: &Address, length: usize) -> Self {
+        let mut instruction = Vec::with_capacity(2 + USIZE_BYTES);
+        instruction.push(Self::CODE);
+        instruction.push(operation as u8);
+        instruction.extend(length.to_le_bytes());
+        instruction.extend(lhs.get_bytes());
+        instruction.extend(rhs.get_bytes());
+        instruction.extend(destination.get_bytes());
+
+        Self { address: program_memory.append(&instruction) }
+    }
+
+    pub fn get_address(&self) -> usize {
+        self.address
+    }
+
+    pub fn execute(runtime_memory: &mut RuntimeMemory) {
+        let operation = BitwiseOperation::from_byte(runtime_memory.program_mut().get_byte());
+        let length = runtime_memory.program_mut().get_usize();
+        let lhs = runtime_memory.program_mut().get_address(Some(length));
+        let rhs = runtime_memory.program_mut().get_address(Some(length));
+        let destination = runtime_memory.program_mut().get_address(None);
+
+        let lhs = runtime_memory.read(lhs, length);
+        let rhs = runtime_memory.read(rhs, length);
+
+        let result: Vec<u8> = lhs.iter().zip(rhs.iter()).map(|(l, r)| match operation {
+            BitwiseOperation::And => l & r,
+            BitwiseOperation::Or => l | r,
+            BitwiseOperation::Xor => l ^ r,
+        }).collect();
+        runtime_memory.write(destination, &result);
+    }
+}
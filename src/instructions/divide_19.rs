@@ -0,0 +1,44 @@
+use crate::memory::address::Address;
+use crate::memory::MemoryManager;
+use crate::memory::runtime_memory::RuntimeMemory;
+use crate::util::{int_to_bytes, read_int, USIZE_BYTES};
+
+/// Divides two integers of the same width, rounding towards zero. The divisor must be checked for
+/// zero before this instruction
+pub struct DivideInstruction {
+    address: usize,
+}
+
+impl DivideInstruction {
+    pub const CODE: u8 = 19;
+
+    pub fn new_alloc(program_memory: &mut MemoryManager, lhs: &Address, rhs: &Address, destination: &Address, length: usize, signed: bool) -> Self {
+        let mut instruction = Vec::with_capacity(2 + USIZE_BYTES);
+        instruction.push(Self::CODE);
+        instruction.extend(length.to_le_bytes());
+        instruction.push(signed as u8);
+        instruction.extend(lhs.get_bytes());
+        instruction.extend(rhs.get_bytes());
+        instruction.extend(destination.get_bytes());
+
+        Self { address: program_memory.append(&instruction) }
+    }
+
+    pub fn get_address(&self) -> usize {
+        self.address
+    }
+
+    pub fn execute(runtime_memory: &mut RuntimeMemory) {
+        let length = runtime_memory.program_mut().get_usize();
+        let signed = runtime_memory.program_mut().get_byte() != 0;
+        let lhs = runtime_memory.program_mut().get_address(Some(length));
+        let rhs = runtime_memory.program_mut().get_address(Some(length));
+        let destination = runtime_memory.program_mut().get_address(None);
+
+        let lhs = read_int(&runtime_memory.read(lhs, length), signed);
+        let rhs = read_int(&runtime_memory.read(rhs, length), signed);
+
+        //? Values are widened so MIN / -1 wraps when truncated instead of overflowing
+        runtime_memory.write(destination, &int_to_bytes(lhs / rhs, length));
+    }
+}
@@ -1,7 +1,8 @@
+use crate::memory::runtime_memory::RuntimeMemory;
 
 pub struct ExampleInstruction;
 impl ExampleInstruction {
     pub const CODE: u8 = 12;
 
-    pub fn execute() {}
-}
\ No newline at end of file
+    pub fn execute(_runtime_memory: &mut RuntimeMemory) {}
+}
@@ -0,0 +1,42 @@
+use crate::memory::address::Address;
+use crate::memory::MemoryManager;
+use crate::memory::runtime_memory::RuntimeMemory;
+use crate::util::{int_to_bytes, read_int, USIZE_BYTES};
+
+/// Converts an integer to a different width. Widening sign-extends signed sources and
+/// zero-extends unsigned ones, narrowing keeps the low bytes
+pub struct ExtendInstruction {
+    address: usize,
+}
+
+impl ExtendInstruction {
+    pub const CODE: u8 = 21;
+
+    pub fn new_alloc(program_memory: &mut MemoryManager, source: &Address, source_length: usize, destination: &Address, destination_length: usize, signed: bool) -> Self {
+        let mut instruction = Vec::with_capacity(2 + 2 * USIZE_BYTES);
+        instruction.push(Self::CODE);
+        instruction.extend(source_length.to_le_bytes());
+        instruction.extend(destination_length.to_le_bytes());
+        instruction.push(signed as u8);
+        instruction.extend(source.get_bytes());
+        instruction.extend(destination.get_bytes());
+
+        Self { address: program_memory.append(&instruction) }
+    }
+
+    pub fn get_address(&self) -> usize {
+        self.address
+    }
+
+    pub fn execute(runtime_memory: &mut RuntimeMemory) {
+        let source_length = runtime_memory.program_mut().get_usize();
+        let destination_length = runtime_memory.program_mut().get_usize();
+        let signed = runtime_memory.program_mut().get_byte() != 0;
+        let source = runtime_memory.program_mut().get_address(Some(source_length));
+        let destination = runtime_memory.program_mut().get_address(None);
+
+        let value = read_int(&runtime_memory.read(source, source_length), signed);
+
+        runtime_memory.write(destination, &int_to_bytes(value, destination_length));
+    }
+}
@@ -0,0 +1,57 @@
+use crate::instructions::float_operation_22::{read_float, FLOAT_BYTES};
+use crate::memory::address::Address;
+use crate::memory::MemoryManager;
+use crate::memory::runtime_memory::RuntimeMemory;
+use crate::util::{int_to_bytes, read_int, USIZE_BYTES};
+
+/// Converts between a float and an integer. Floats are truncated towards zero and saturate at the
+/// bounds of the integer (`NaN` becomes zero)
+pub struct FloatConvertInstruction {
+    address: usize,
+}
+
+impl FloatConvertInstruction {
+    pub const CODE: u8 = 23;
+
+    /// If `to_float` the source is an integer of `integer_length` bytes, otherwise the destination is
+    pub fn new_alloc(program_memory: &mut MemoryManager, source: &Address, destination: &Address, to_float: bool, integer_length: usize, signed: bool) -> Self {
+        let mut instruction = Vec::with_capacity(3 + USIZE_BYTES);
+        instruction.push(Self::CODE);
+        instruction.push(to_float as u8);
+        instruction.extend(integer_length.to_le_bytes());
+        instruction.push(signed as u8);
+        instruction.extend(source.get_bytes());
+        instruction.extend(destination.get_bytes());
+
+        Self { address: program_memory.append(&instruction) }
+    }
+
+    pub fn get_address(&self) -> usize {
+        self.address
+    }
+
+    pub fn execute(runtime_memory: &mut RuntimeMemory) {
+        let to_float = runtime_memory.program_mut().get_byte() != 0;
+        let integer_length = runtime_memory.program_mut().get_usize();
+        let signed = runtime_memory.program_mut().get_byte() != 0;
+        let source_length = if to_float { integer_length } else { FLOAT_BYTES };
+        let source = runtime_memory.program_mut().get_address(Some(source_length));
+        let destination = runtime_memory.program_mut().get_address(None);
+
+        let source = runtime_memory.read(source, source_length);
+        if to_float {
+            let value = read_int(&source, signed) as f64;
+            runtime_memory.write(destination, &value.to_le_bytes());
+            return;
+        }
+
+        let bits = integer_length as u32 * 8;
+        let (min, max) = if signed {
+            (-(1i128 << (bits - 1)), (1i128 << (bits - 1)) - 1)
+        } else {
+            (0, (1i128 << bits) - 1)
+        };
+        let value = (read_float(&source) as i128).clamp(min, max);
+        runtime_memory.write(destination, &int_to_bytes(value, integer_length));
+    }
+}
@@ -0,0 +1,82 @@
+use crate::memory::address::Address;
+use crate::memory::MemoryManager;
+use crate::memory::runtime_memory::RuntimeMemory;
+
+pub const FLOAT_BYTES: usize = 8;
+
+/// An IEEE-754 operation between two floats. Comparisons are IEEE-754 comparisons rather than
+/// byte comparisons so `NaN` is never equal to anything and `0.0 == -0.0`
+#[derive(Copy, Clone)]
+pub enum FloatOperation {
+    Add = 0,
+    Subtract = 1,
+    Multiply = 2,
+    Divide = 3,
+    Less = 4,
+    LessEqual = 5,
+    Equal = 6,
+}
+
+impl FloatOperation {
+    fn from_byte(byte: u8) -> FloatOperation {
+        match byte {
+            0 => FloatOperation::Add,
+            1 => FloatOperation::Subtract,
+            2 => FloatOperation::Multiply,
+            3 => FloatOperation::Divide,
+            4 => FloatOperation::Less,
+            5 => FloatOperation::LessEqual,
+            6 => FloatOperation::Equal,
+            _ => panic!("Invalid float operation '{}'", byte),
+        }
+    }
+}
+
+pub struct FloatOperationInstruction {
+    address: usize,
+}
+
+impl FloatOperationInstruction {
+    pub const CODE: u8 = 22;
+
+    pub fn new_alloc(program_memory: &mut MemoryManager, operation: FloatOperation, lhs: &Address, rhs: &Address, destination: &Address) -> Self {
+        let mut instruction = Vec::with_capacity(2);
+        instruction.push(Self::CODE);
+        instruction.push(operation as u8);
+        instruction.extend(lhs.get_bytes());
+        instruction.extend(rhs.get_bytes());
+        instruction.extend(destination.get_bytes());
+
+        Self { address: program_memory.append(&instruction) }
+    }
+
+    pub fn get_address(&self) -> usize {
+        self.address
+    }
+
+    pub fn execute(runtime_memory: &mut RuntimeMemory) {
+        let operation = FloatOperation::from_byte(runtime_memory.program_mut().get_byte());
+        let lhs = runtime_memory.program_mut().get_address(Some(FLOAT_BYTES));
+        let rhs = runtime_memory.program_mut().get_address(Some(FLOAT_BYTES));
+        let destination = runtime_memory.program_mut().get_address(None);
+
+        let lhs = read_float(&runtime_memory.read(lhs, FLOAT_BYTES));
+        let rhs = read_float(&runtime_memory.read(rhs, FLOAT_BYTES));
+
+        //? Booleans are all ones when true
+        let comparison = |result: bool| if result { 0xFF } else { 0x00 };
+        match operation {
+            FloatOperation::Add => runtime_memory.write(destination, &(lhs + rhs).to_le_bytes()),
+            FloatOperation::Subtract => runtime_memory.write(destination, &(lhs - rhs).to_le_bytes()),
+            FloatOperation::Multiply => runtime_memory.write(destination, &(lhs * rhs).to_le_bytes()),
+            FloatOperation::Divide => runtime_memory.write(destination, &(lhs / rhs).to_le_bytes()),
+            FloatOperation::Less => runtime_memory.write(destination, &[comparison(lhs < rhs)]),
+            FloatOperation::LessEqual => runtime_memory.write(destination, &[comparison(lhs <= rhs)]),
+            FloatOperation::Equal => runtime_memory.write(destination, &[comparison(lhs == rhs)]),
+        }
+    }
+}
+
+pub fn read_float(data: &[u8]) -> f64 {
+    f64::from_le_bytes(data[..FLOAT_BYTES].try_into().unwrap())
+}
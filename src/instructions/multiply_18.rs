@@ -0,0 +1,41 @@
+use crate::memory::address::Address;
+use crate::memory::MemoryManager;
+use crate::memory::runtime_memory::RuntimeMemory;
+use crate::util::{int_to_bytes, read_int, USIZE_BYTES};
+
+/// Multiplies two integers of the same width, wrapping on overflow. The low bytes of a product
+/// don't depend on signedness so this is used for both signed and unsigned integers
+pub struct MultiplyInstruction {
+    address: usize,
+}
+
+impl MultiplyInstruction {
+    pub const CODE: u8 = 18;
+
+    pub fn new_alloc(program_memory: &mut MemoryManager, lhs: &Address, rhs: &Address, destination: &Address, length: usize) -> Self {
+        let mut instruction = Vec::with_capacity(1 + USIZE_BYTES);
+        instruction.push(Self::CODE);
+        instruction.extend(length.to_le_bytes());
+        instruction.extend(lhs.get_bytes());
+        instruction.extend(rhs.get_bytes());
+        instruction.extend(destination.get_bytes());
+
+        Self { address: program_memory.append(&instruction) }
+    }
+
+    pub fn get_address(&self) -> usize {
+        self.address
+    }
+
+    pub fn execute(runtime_memory: &mut RuntimeMemory) {
+        let length = runtime_memory.program_mut().get_usize();
+        let lhs = runtime_memory.program_mut().get_address(Some(length));
+        let rhs = runtime_memory.program_mut().get_address(Some(length));
+        let destination = runtime_memory.program_mut().get_address(None);
+
+        let lhs = read_int(&runtime_memory.read(lhs, length), false);
+        let rhs = read_int(&runtime_memory.read(rhs, length), false);
+
+        runtime_memory.write(destination, &int_to_bytes(lhs.wrapping_mul(rhs), length));
+    }
+}
@@ -0,0 +1,51 @@
+use std::process;
+use either::{Left, Right};
+use crate::memory::address::Address;
+use crate::memory::MemoryManager;
+use crate::memory::runtime_memory::RuntimeMemory;
+use crate::util::USIZE_BYTES;
+
+const CONDITION_BYTES: usize = 1;
+
+/// Stops execution with a message if a boolean is false
+pub struct PanicIfNotInstruction {
+    address: usize,
+}
+
+impl PanicIfNotInstruction {
+    pub const CODE: u8 = 17;
+
+    /// Adds the instruction. `message` should describe the failure - the current source location
+    /// is prepended to it
+    pub fn new_alloc(program_memory: &mut MemoryManager, condition: &Address, message: &str) -> Self {
+        let message = format!("{}: {}", program_memory.get_source_location(), message);
+
+        let mut instruction = Vec::with_capacity(1 + USIZE_BYTES + message.len());
+        instruction.push(Self::CODE);
+        instruction.extend(condition.get_bytes());
+        instruction.extend(message.len().to_le_bytes());
+        instruction.extend(message.as_bytes());
+
+        Self { address: program_memory.append(&instruction) }
+    }
+
+    pub fn get_address(&self) -> usize {
+        self.address
+    }
+
+    pub fn execute(runtime_memory: &mut RuntimeMemory) {
+        let condition = runtime_memory.program_mut().get_address(Some(CONDITION_BYTES));
+        let message_length = runtime_memory.program_mut().get_usize();
+        let message = String::from_utf8_lossy(runtime_memory.program_mut().get_slice(message_length)).to_string();
+
+        let condition = match condition.evaluate_address_to_data(runtime_memory) {
+            Left(data) => data[0],
+            Right(data) => data[0],
+        };
+
+        if condition == 0 {
+            eprintln!("Runtime error - {}", message);
+            process::exit(1);
+        }
+    }
+}
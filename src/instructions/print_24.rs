@@ -0,0 +1,74 @@
+use crate::instructions::float_operation_22::read_float;
+use crate::memory::address::Address;
+use crate::memory::MemoryManager;
+use crate::memory::runtime_memory::RuntimeMemory;
+use crate::util::{read_int, USIZE_BYTES};
+
+/// How the printed bytes are interpreted
+#[derive(Copy, Clone)]
+pub enum PrintFormat {
+    SignedInteger = 0,
+    UnsignedInteger = 1,
+    Float = 2,
+    Boolean = 3,
+    Character = 4,
+}
+
+impl PrintFormat {
+    fn from_byte(byte: u8) -> PrintFormat {
+        match byte {
+            0 => PrintFormat::SignedInteger,
+            1 => PrintFormat::UnsignedInteger,
+            2 => PrintFormat::Float,
+            3 => PrintFormat::Boolean,
+            4 => PrintFormat::Character,
+            _ => panic!("Invalid print format '{}'", byte),
+        }
+    }
+}
+
+/// Prints a value followed by a newline
+pub struct PrintInstruction {
+    address: usize,
+}
+
+impl PrintInstruction {
+    pub const CODE: u8 = 24;
+
+    pub fn new_alloc(program_memory: &mut MemoryManager, value: &Address, length: usize, format: PrintFormat) -> Self {
+        let mut instruction = Vec::with_capacity(2 + USIZE_BYTES);
+        instruction.push(Self::CODE);
+        instruction.push(format as u8);
+        instruction.extend(length.to_le_bytes());
+        instruction.extend(value.get_bytes());
+
+        Self { address: program_memory.append(&instruction) }
+    }
+
+    pub fn get_address(&self) -> usize {
+        self.address
+    }
+
+    pub fn execute(runtime_memory: &mut RuntimeMemory) {
+        let format = PrintFormat::from_byte(runtime_memory.program_mut().get_byte());
+        let length = runtime_memory.program_mut().get_usize();
+        let value = runtime_memory.program_mut().get_address(Some(length));
+        let value = runtime_memory.read(value, length);
+
+        match format {
+            PrintFormat::SignedInteger => println!("{}", read_int(&value, true)),
+            PrintFormat::UnsignedInteger => println!("{}", read_int(&value, false)),
+            PrintFormat::Float => {
+                //? Always show a decimal point so floats are distinguishable from integers
+                let value = read_float(&value);
+                if value.is_finite() && value.fract() == 0.0 {
+                    println!("{:.1}", value)
+                } else {
+                    println!("{}", value)
+                }
+            }
+            PrintFormat::Boolean => println!("{}", value[0] != 0),
+            PrintFormat::Character => println!("{}", value[0] as char),
+        }
+    }
+}
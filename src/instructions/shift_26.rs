@@ -0,0 +1,54 @@
+use crate::memory::address::Address;
+use crate::memory::MemoryManager;
+use crate::memory::runtime_memory::RuntimeMemory;
+use crate::util::{int_to_bytes, read_int, USIZE_BYTES};
+
+/// Shifts an integer left or right by another integer of the same width. Right shifts of signed
+/// integers are arithmetic. The amount is read as unsigned and shifting by the width of the
+/// integer or more shifts every bit out
+pub struct ShiftInstruction {
+    address: usize,
+}
+
+impl ShiftInstruction {
+    pub const CODE: u8 = 26;
+
+    pub fn new_alloc(program_memory: &mut MemoryManager, lhs: &Address, rhs: &Address, destination: &Address, length: usize, left: bool, signed: bool) -> Self {
+        let mut instruction = Vec::with_capacity(3 + USIZE_BYTES);
+        instruction.push(Self::CODE);
+        instruction.extend(length.to_le_bytes());
+        instruction.push(left as u8);
+        instruction.push(signed as u8);
+        instruction.extend(lhs.get_bytes());
+        instruction.extend(rhs.get_bytes());
+        instruction.extend(destination.get_bytes());
+
+        Self { address: program_memory.append(&instruction) }
+    }
+
+    pub fn get_address(&self) -> usize {
+        self.address
+    }
+
+    pub fn execute(runtime_memory: &mut RuntimeMemory) {
+        let length = runtime_memory.program_mut().get_usize();
+        let left = runtime_memory.program_mut().get_byte() != 0;
+        let signed = runtime_memory.program_mut().get_byte() != 0;
+        let lhs = runtime_memory.program_mut().get_address(Some(length));
+        let rhs = runtime_memory.program_mut().get_address(Some(length));
+        let destination = runtime_memory.program_mut().get_address(None);
+
+        let value = read_int(&runtime_memory.read(lhs, length), signed);
+        let amount = read_int(&runtime_memory.read(rhs, length), false);
+
+        let bits = length as i128 * 8;
+        //? Values are widened so the sign is kept for arithmetic right shifts
+        let result = match (left, amount >= bits) {
+            (true, true) => 0,
+            (true, false) => value << amount,
+            (false, true) => value >> 127,
+            (false, false) => value >> amount,
+        };
+        runtime_memory.write(destination, &int_to_bytes(result, length));
+    }
+}
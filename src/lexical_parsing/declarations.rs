@@ -0,0 +1,72 @@
+use crate::lexical_parsing::symbols::{Assigner, Block, Keyword, Punctuation, Symbol};
+
+/// A declared name and its path (see `SymbolSpan::get_nested`)
+pub type Declaration<'a> = (&'a Vec<String>, Vec<usize>);
+
+/// Returns the declarations made by a line along with the symbols that make up the value
+pub fn get_declarations(symbols: &[Symbol]) -> (Vec<Declaration<'_>>, &[Symbol]) {
+    let setter = symbols
+        .iter()
+        .position(|s| matches!(s, Symbol::Assigner(Assigner::Setter)));
+    let (declaration, value) = match setter {
+        Some(setter) => (&symbols[..setter], &symbols[setter + 1..]),
+        None => (symbols, &symbols[symbols.len()..]),
+    };
+
+    match declaration {
+        [Symbol::Keyword(Keyword::Let), Symbol::Name(name)] => (vec![(name, vec![1])], value),
+        [Symbol::Keyword(Keyword::Const), Symbol::Type(_), Symbol::Name(name)] => {
+            (vec![(name, vec![2])], value)
+        }
+        [Symbol::Type(_) | Symbol::List(_) | Symbol::Name(_), Symbol::Name(name)] => {
+            (vec![(name, vec![1])], value)
+        }
+        //? Destructuring e.g. int a, bool b = (5, true)
+        _ if setter.is_some() => {
+            let mut declarations = Vec::new();
+            let mut start = 0;
+            for part in
+                declaration.split(|s| matches!(s, Symbol::Punctuation(Punctuation::ListSeparator)))
+            {
+                match part {
+                    [Symbol::Type(_) | Symbol::List(_), Symbol::Name(name)] => {
+                        declarations.push((name, vec![start + 1]))
+                    }
+                    _ => return (Vec::new(), symbols),
+                }
+                start += part.len() + 1;
+            }
+            (declarations, value)
+        }
+        _ => (Vec::new(), symbols),
+    }
+}
+
+/// Returns the parameters of a function declaration e.g. `fn name (int a, int b)`
+pub fn get_parameters(symbols: &[Symbol]) -> Vec<Declaration<'_>> {
+    match symbols.get(2) {
+        Some(Symbol::List(parameters)) => parameters
+            .iter()
+            .enumerate()
+            .filter_map(|(i, parameter)| match parameter.as_slice() {
+                [_, Symbol::Name(name)] => Some((name, vec![2, i, 1])),
+                _ => None,
+            })
+            .collect(),
+        Some(Symbol::BracketedSection(parameter)) => match parameter.as_slice() {
+            [_, Symbol::Name(name)] => vec![(name, vec![2, 1])],
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the name declared by a function, class or enum line e.g. `fn name (int a)`
+pub fn get_block_declaration(symbols: &[Symbol]) -> Option<Declaration<'_>> {
+    match symbols {
+        [Symbol::Block(Block::Function | Block::Class | Block::Enum), Symbol::Name(name), ..] => {
+            Some((name, vec![1]))
+        }
+        _ => None,
+    }
+}
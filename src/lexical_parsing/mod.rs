@@ -1,3 +1,5 @@
-mod preprocessor;
+pub mod declarations;
+pub mod modules;
+pub mod preprocessor;
 pub mod symbols;
-pub mod simple_line_error;
\ No newline at end of file
+pub mod span;
\ No newline at end of file
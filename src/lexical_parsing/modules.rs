@@ -0,0 +1,378 @@
+use crate::diagnostics::{Diagnostic, Label, MODULE_ERROR};
+use crate::lexical_parsing::declarations::{
+    get_block_declaration, get_declarations, get_parameters,
+};
+use crate::lexical_parsing::preprocessor::SymbolData;
+use crate::lexical_parsing::symbols::{Block, Symbol};
+
+/// Separates a module's namespace from the names declared in it e.g. `utils::add`
+pub const MODULE_SEPARATOR: &str = "::";
+
+/// The namespace and imports of a file
+pub struct Module {
+    /// Prefixed to the file's top level names to keep them apart from other files' names. `None`
+    /// for the file being compiled, whose names are left as they are
+    pub namespace: Option<String>,
+    /// The alias of each import and the index of the imported file
+    pub imports: Vec<(String, usize)>,
+    /// Top level names marked `pub`
+    pub exports: Vec<String>,
+    /// All top level names, filled in once the file's names have been resolved
+    pub declarations: Vec<String>,
+    /// Where the file was first imported. `None` for the file being compiled
+    pub imported_at: Option<Label>,
+    /// Whether the file and all of its imports have finished loading
+    pub loaded: bool,
+}
+
+impl Module {
+    pub fn new(namespace: Option<String>) -> Module {
+        Module {
+            namespace,
+            imports: Vec::new(),
+            exports: Vec::new(),
+            declarations: Vec::new(),
+            imported_at: None,
+            loaded: false,
+        }
+    }
+
+    /// Returns the name a top level declaration is known by after resolution
+    pub fn get_qualified_name(&self, name: &str) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("{}{}{}", namespace, MODULE_SEPARATOR, name),
+            None => name.to_string(),
+        }
+    }
+
+    pub fn get_import(&self, alias: &str) -> Option<usize> {
+        self.imports
+            .iter()
+            .find(|(a, _)| a == alias)
+            .map(|(_, index)| *index)
+    }
+}
+
+/// Returns the single part names declared by a top level line
+fn get_top_level_names(symbols: &[Symbol]) -> Vec<(String, Vec<usize>)> {
+    let declarations = match get_block_declaration(symbols) {
+        Some(declaration) => vec![declaration],
+        None => get_declarations(symbols).0,
+    };
+    declarations
+        .into_iter()
+        .filter(|(name, _)| name.len() == 1)
+        .map(|(name, path)| (name[0].clone(), path))
+        .collect()
+}
+
+/// Returns the single part names declared by a top level line, for use with `pub`
+pub fn get_exported_names(symbols: &[Symbol]) -> Vec<String> {
+    get_top_level_names(symbols)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect()
+}
+
+struct NameResolver<'a> {
+    symbol_data: &'a SymbolData,
+    module: &'a Module,
+    /// Names declared inside blocks, with the indentation of the block's contents
+    local_scopes: Vec<(usize, Vec<String>)>,
+    line_index: usize,
+    /// The paths of names declared at the top level of the current line
+    declared_paths: Vec<Vec<usize>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> NameResolver<'a> {
+    fn error(&mut self, message: String, path: &[usize]) -> Diagnostic {
+        Diagnostic::error(message)
+            .with_code(MODULE_ERROR)
+            .with_primary(
+                self.symbol_data
+                    .get_label(self.line_index, path, String::new()),
+            )
+    }
+
+    fn is_local(&self, name: &str) -> bool {
+        self.local_scopes
+            .iter()
+            .any(|(_, names)| names.iter().any(|n| n == name))
+    }
+
+    /// Returns the qualified form of `name`, or `None` if it doesn't change
+    fn resolve_name(&mut self, name: &[String], path: &[usize]) -> Option<Vec<String>> {
+        //? Declarations reusing an alias have already been reported
+        let import = if self.declared_paths.iter().any(|p| p == path) {
+            None
+        } else {
+            self.module.get_import(&name[0])
+        };
+        if let Some(import) = import {
+            let alias = &name[0];
+            let Some(member) = name.get(1) else {
+                let diagnostic = self.error(
+                    format!(
+                        "Module '{}' can't be used as a value - use {}.[Name] to access its members",
+                        alias, alias
+                    ),
+                    path,
+                );
+                self.diagnostics.push(diagnostic);
+                return None;
+            };
+
+            let target = &self.symbol_data.modules[import];
+            if !target.declarations.contains(member) {
+                let diagnostic = self.error(
+                    format!("'{}' not found in module '{}'", member, alias),
+                    path,
+                );
+                self.diagnostics.push(diagnostic);
+                return None;
+            }
+            if !target.exports.contains(member) {
+                let diagnostic = self
+                    .error(
+                        format!("'{}' is private to module '{}'", member, alias),
+                        path,
+                    )
+                    .with_help("Mark the declaration 'pub' to use it from other files".to_string());
+                self.diagnostics.push(diagnostic);
+                return None;
+            }
+
+            let mut qualified = vec![target.get_qualified_name(member)];
+            qualified.extend_from_slice(&name[2..]);
+            return Some(qualified);
+        }
+
+        if self.module.namespace.is_some()
+            && self.module.declarations.contains(&name[0])
+            && !self.is_local(&name[0])
+        {
+            let mut qualified = vec![self.module.get_qualified_name(&name[0])];
+            qualified.extend_from_slice(&name[1..]);
+            return Some(qualified);
+        }
+
+        None
+    }
+
+    /// Resolves every name in `symbols`, following `path` (see `SymbolSpan::get_nested`) for
+    /// error locations
+    fn resolve_symbols(&mut self, symbols: &mut [Symbol], path: &mut Vec<usize>) {
+        for (i, symbol) in symbols.iter_mut().enumerate() {
+            path.push(i);
+            match symbol {
+                Symbol::Name(name) => {
+                    if let Some(qualified) = self.resolve_name(name, path) {
+                        *name = qualified;
+                    }
+                }
+                Symbol::BracketedSection(symbols) => self.resolve_symbols(symbols, path),
+                Symbol::List(items) => {
+                    for (j, item) in items.iter_mut().enumerate() {
+                        path.push(j);
+                        self.resolve_symbols(item, path);
+                        path.pop();
+                    }
+                }
+                Symbol::Indexer(indexed, index) => {
+                    self.resolve_symbols(std::slice::from_mut(indexed.as_mut()), path);
+                    path.push(1);
+                    self.resolve_symbols(index, path);
+                    path.pop();
+                }
+                Symbol::MethodCall(value, _, arguments) => {
+                    self.resolve_symbols(std::slice::from_mut(value.as_mut()), path);
+                    for argument in arguments {
+                        self.resolve_symbols(argument, path);
+                    }
+                }
+                _ => {}
+            }
+            path.pop();
+        }
+    }
+
+    /// Records the names declared by a line inside a block so they aren't qualified
+    fn declare_locals(&mut self, symbols: &[Symbol], indentation: usize) {
+        while self
+            .local_scopes
+            .last()
+            .is_some_and(|(i, _)| *i > indentation)
+        {
+            self.local_scopes.pop();
+        }
+
+        if indentation > 0 {
+            if self.local_scopes.last().map(|(i, _)| *i) != Some(indentation) {
+                self.local_scopes.push((indentation, Vec::new()));
+            }
+            let names: Vec<String> = get_declarations(symbols)
+                .0
+                .into_iter()
+                .chain(get_block_declaration(symbols))
+                .filter(|(name, _)| name.len() == 1)
+                .map(|(name, _)| name[0].clone())
+                .collect();
+            self.local_scopes.last_mut().unwrap().1.extend(names);
+        }
+
+        if let Some(Symbol::Block(Block::Function)) = symbols.first() {
+            let parameters = get_parameters(symbols)
+                .into_iter()
+                .filter(|(name, _)| name.len() == 1)
+                .map(|(name, _)| name[0].clone())
+                .collect();
+            self.local_scopes.push((indentation + 1, parameters));
+        }
+    }
+}
+
+/// Qualifies the names used in a file so that each file has its own namespace:
+/// * Top level names declared in an imported file are prefixed with its namespace
+/// * `alias.name` refers to the exported name `name` of the file imported as `alias`
+///
+/// Must be called once the file and all of its imports have been loaded. Returns any errors
+/// found
+pub fn resolve_names(symbol_data: &mut SymbolData, file_name_index: usize) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    //? Find the top level names
+    let mut declarations = Vec::new();
+    for (line_index, line) in symbol_data.lines.iter().enumerate() {
+        if line.file_name_index != file_name_index || line.indentation != 0 {
+            continue;
+        }
+        for (name, path) in get_top_level_names(&line.symbols) {
+            if symbol_data.modules[file_name_index]
+                .get_import(&name)
+                .is_some()
+            {
+                diagnostics.push(
+                    Diagnostic::error(format!("'{}' is already used as an import alias", name))
+                        .with_code(MODULE_ERROR)
+                        .with_primary(symbol_data.get_label(line_index, &path, String::new())),
+                );
+            }
+            declarations.push(name);
+        }
+    }
+    symbol_data.modules[file_name_index].declarations = declarations;
+
+    //? Qualify names in copies of each line's symbols as the symbol data is borrowed meanwhile
+    let mut resolved = Vec::new();
+    {
+        let mut resolver = NameResolver {
+            symbol_data,
+            module: &symbol_data.modules[file_name_index],
+            local_scopes: Vec::new(),
+            line_index: 0,
+            declared_paths: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+        for (line_index, line) in symbol_data.lines.iter().enumerate() {
+            if line.file_name_index != file_name_index {
+                continue;
+            }
+            let mut symbols = line.symbols.clone();
+            resolver.line_index = line_index;
+            resolver.declared_paths = match line.indentation {
+                0 => get_top_level_names(&symbols)
+                    .into_iter()
+                    .map(|(_, path)| path)
+                    .collect(),
+                _ => Vec::new(),
+            };
+            resolver.declare_locals(&symbols, line.indentation);
+            resolver.resolve_symbols(&mut symbols, &mut Vec::new());
+            resolved.push((line_index, symbols));
+        }
+        diagnostics.append(&mut resolver.diagnostics);
+    }
+
+    for (line_index, symbols) in resolved {
+        symbol_data.lines[line_index].symbols = symbols;
+    }
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diagnostics::{DiagnosticCollector, DEFAULT_ERROR_LIMIT};
+    use crate::lexical_parsing::preprocessor::{convert_to_symbols, SymbolData};
+    use crate::lexical_parsing::symbols::Symbol;
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// Writes `files` into a new directory and loads its `main.why`
+    fn load(name: &str, files: &[(&str, &str)]) -> (SymbolData, DiagnosticCollector) {
+        let directory = std::env::temp_dir().join(format!("whython_modules_{}", name));
+        let _ = fs::remove_dir_all(&directory);
+        for (file_name, source) in files {
+            let path: PathBuf = directory.join(file_name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, source).unwrap();
+        }
+
+        let mut symbol_data = SymbolData::new();
+        let mut diagnostics = DiagnosticCollector::new(DEFAULT_ERROR_LIMIT);
+        convert_to_symbols(
+            directory.join("main.why").to_string_lossy().to_string(),
+            &mut symbol_data,
+            &mut diagnostics,
+        )
+        .unwrap();
+        (symbol_data, diagnostics)
+    }
+
+    #[test]
+    fn keeps_same_named_files_apart() {
+        let (symbol_data, diagnostics) = load(
+            "same_named_files",
+            &[
+                (
+                    "main.why",
+                    "import a.user.why as first\nimport b.user.why as second\n",
+                ),
+                ("a/user.why", "import lib.why\n"),
+                ("b/user.why", "import lib.why\n"),
+                ("a/lib.why", "pub int value = 1\n"),
+                ("b/lib.why", "pub int value = 2\n"),
+            ],
+        );
+        assert!(!diagnostics.has_errors());
+
+        let mut declared: Vec<&str> = symbol_data
+            .lines
+            .iter()
+            .flat_map(|line| &line.symbols)
+            .filter_map(|symbol| match symbol {
+                Symbol::Name(name) if name[0].ends_with("::value") => Some(name[0].as_str()),
+                _ => None,
+            })
+            .collect();
+        declared.dedup();
+        assert_eq!(declared.len(), 2);
+    }
+
+    #[test]
+    fn reports_clashing_imports() {
+        let (_, diagnostics) = load(
+            "clashing_imports",
+            &[
+                ("main.why", "import a.lib.why\nimport b.lib.why\n"),
+                ("a/lib.why", "pub int value = 1\n"),
+                ("b/lib.why", "pub int value = 2\n"),
+            ],
+        );
+        assert!(diagnostics
+            .get_diagnostics()
+            .iter()
+            .any(|d| d.message == "'lib' is already used as an import alias"));
+    }
+}
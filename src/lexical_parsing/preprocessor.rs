@@ -1,41 +1,192 @@
 use crate::error::BoxedError;
-use crate::file_util::load_file;
-use crate::lexical_parsing::simple_line_error::SimpleLineError;
+use crate::file_util::{find_import, get_canonical_path, load_file};
+use crate::diagnostics::{
+    Diagnostic, DiagnosticCollector, Label, SourceProvider, IMPORT_ERROR, INDENTATION_ERROR,
+    MODULE_ERROR, SYMBOL_ERROR,
+};
+use crate::lexical_parsing::declarations::get_block_declaration;
+use crate::lexical_parsing::modules::{
+    get_exported_names, resolve_names, Module, MODULE_SEPARATOR,
+};
+use crate::lexical_parsing::span::{LineMap, Span, SpanError, SymbolSpan};
 use crate::lexical_parsing::symbols::{
-    get_all_symbol, Keyword, Punctuation, Symbol, CHAR_DELIMITER, LIST_SEPARATOR_CHARACTER,
-    STRING_DELIMITER,
+    format_escape_codes, get_all_symbol, Keyword, Literal, Operator, Punctuation, Symbol,
+    TypeSymbol, CHAR_DELIMITER, HEX_PREFIX, LIST_SEPARATOR_CHARACTER, OPTIONAL_OPEN,
+    STRING_DELIMITER, STRING_ESCAPE_CHAR, TYPED_POINTER_OPEN, TYPE_PARAMETER_CLOSE,
 };
-use crate::util::{join_file_name};
+use crate::util::get_import_path;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
 pub const COMMENT_CHARACTER: char = '#';
+pub const BLOCK_COMMENT_OPEN: &str = "#[";
+pub const BLOCK_COMMENT_CLOSE: &str = "]#";
+/// Lines starting with this document the declaration that follows them
+pub const DOC_COMMENT_START: &str = "##";
+/// Ending a line with this joins it with the next
+pub const CONTINUATION_CHARACTER: char = '\\';
 pub const OPEN_BRACKET_CHARACTER: char = '(';
 pub const CLOSE_BRACKET_CHARACTER: char = ')';
 pub const OPEN_INDEXER_CHARACTER: char = '[';
 pub const CLOSE_INDEXER_CHARACTER: char = ']';
+pub const OPERATOR_CHARACTERS: &str = "+-*/%<>=!&|^~";
+/// The length of the longest operator or assigner e.g. `<<=`
+pub const MAX_OPERATOR_LENGTH: usize = 3;
+/// Lists the import graph instead of compiling (see `SymbolData::get_import_graph`)
+pub const IMPORT_GRAPH_FLAG: &str = "--imports";
+/// Lists the doc comment of each documented declaration instead of compiling
+pub const DOC_COMMENTS_FLAG: &str = "--docs";
+
+fn is_operator_character(c: char) -> bool {
+    OPERATOR_CHARACTERS.contains(c)
+}
+
+/// Returns whether `buffer` followed by `<` starts a type parameter e.g. `ptr<int>`
+fn is_type_parameter_start(buffer: &str, type_parameter_depth: usize) -> bool {
+    let opened = format!("{buffer}<");
+    if type_parameter_depth == 0 {
+        opened == TYPED_POINTER_OPEN || opened == OPTIONAL_OPEN
+    } else {
+        opened.ends_with(TYPED_POINTER_OPEN) || opened.ends_with(OPTIONAL_OPEN)
+    }
+}
 
-/// Takes a line of code and returns an array of symbols
+/// Returns whether `buffer` is a float literal awaiting the sign of its exponent e.g. `1e`. `e`
+/// is a digit in hexadecimal literals e.g. `0x1e`
+fn is_exponent_start(buffer: &str) -> bool {
+    buffer.starts_with(|c: char| c.is_ascii_digit())
+        && !buffer.starts_with(HEX_PREFIX)
+        && buffer.ends_with(['e', 'E'])
+}
+
+/// Takes a line of code and returns an array of symbols along with the columns each symbol
+/// covers. `offset` is the column `line` starts at
 #[allow(clippy::single_match)]
-pub fn get_symbols_from_line(line: &str) -> Result<Vec<Symbol>, String> {
-    fn process_buffer(buffer: &mut String, symbol_line: &mut Vec<Symbol>) -> Result<(), String> {
+pub fn get_symbols_and_spans_from_line(
+    line: &str,
+    offset: usize,
+) -> Result<(Vec<Symbol>, Vec<SymbolSpan>), SpanError> {
+    fn process_buffer(
+        buffer: &mut String,
+        buffer_start: usize,
+        symbol_line: &mut Vec<Symbol>,
+        spans: &mut Vec<SymbolSpan>,
+    ) -> Result<(), SpanError> {
         if buffer.is_empty() {
             return Ok(());
         }
+        let buffer_span = Span::new(buffer_start, buffer_start + buffer.chars().count());
 
-        let symbol = get_all_symbol(buffer)?;
-        symbol_line.push(symbol);
+        //? Operator characters with no whitespace between them e.g. !=- -> != -
+        if buffer.starts_with(is_operator_character) {
+            let mut start = 0;
+            while start < buffer.len() {
+                let (symbol, length) = get_longest_operator(&buffer[start..])
+                    .map_err(|e| SpanError::new(e, buffer_span))?;
+                let column = buffer_start + start;
+                push_symbol(
+                    symbol,
+                    SymbolSpan::leaf(column, column + length),
+                    symbol_line,
+                    spans,
+                );
+                start += length;
+            }
+            buffer.clear();
+            return Ok(());
+        }
+
+        let symbol = get_all_symbol(buffer).map_err(|e| SpanError::new(e, buffer_span))?;
+        push_symbol(
+            symbol,
+            SymbolSpan::new(buffer_span, Vec::new()),
+            symbol_line,
+            spans,
+        );
         buffer.clear();
         Ok(())
     }
 
+    /// Returns the longest operator or assigner at the start of `string` and its length
+    fn get_longest_operator(string: &str) -> Result<(Symbol, usize), String> {
+        for length in (1..=string.len().min(MAX_OPERATOR_LENGTH)).rev() {
+            if let Ok(symbol @ (Symbol::Operator(_) | Symbol::Assigner(_))) =
+                get_all_symbol(&string[..length])
+            {
+                return Ok((symbol, length));
+            }
+        }
+
+        Err(format!("Operator '{}' not recognised", string))
+    }
+
+    /// Pushes a symbol, treating `-` as negation when it isn't preceded by a value and folding
+    /// negated numeric literals into a single literal
+    fn push_symbol(
+        symbol: Symbol,
+        span: SymbolSpan,
+        symbol_line: &mut Vec<Symbol>,
+        spans: &mut Vec<SymbolSpan>,
+    ) {
+        let follows_value = matches!(
+            symbol_line.last(),
+            Some(
+                Symbol::Literal(_)
+                    | Symbol::Name(_)
+                    | Symbol::BracketedSection(_)
+                    | Symbol::Indexer(..)
+                    | Symbol::List(_)
+                    | Symbol::MethodCall(..)
+            )
+        );
+        let follows_negation = symbol_line.last() == Some(&Symbol::Operator(Operator::Negate));
+
+        let negated = match symbol {
+            Symbol::Operator(Operator::Subtract) if !follows_value => {
+                symbol_line.push(Symbol::Operator(Operator::Negate));
+                spans.push(span);
+                return;
+            }
+            Symbol::Literal(Literal::Int(value)) if follows_negation => {
+                Symbol::Literal(Literal::Int(-value))
+            }
+            Symbol::Literal(Literal::TypedInt(value, t)) if follows_negation => {
+                Symbol::Literal(Literal::TypedInt(-value, t))
+            }
+            Symbol::Literal(Literal::Float(value)) if follows_negation => {
+                Symbol::Literal(Literal::Float(-value))
+            }
+            symbol => {
+                symbol_line.push(symbol);
+                spans.push(span);
+                return;
+            }
+        };
+
+        *symbol_line.last_mut().unwrap() = negated;
+        let negation_span = spans.last_mut().unwrap();
+        negation_span.span = negation_span.span.join(&span.span);
+    }
+
     let mut symbol_line = Vec::new();
+    let mut spans = Vec::new();
 
     let mut buffer = String::new();
+    //? Column of the first character in the buffer, or of the opening bracket
+    let mut buffer_start = offset;
     let mut in_string: Option<char> = None; // Option<delimiter>
     let mut bracket_depth = 0;
     let mut indexer_depth = 0;
+    let mut type_parameter_depth: usize = 0;
     let mut next_character_escaped = false;
+    //? Column of the open string's delimiter and its position in the buffer
+    let mut string_start = 0;
+    let mut string_buffer_start = 0;
+
+    for (column, c) in line.chars().enumerate() {
+        let column = column + offset;
+        let at_top_level = bracket_depth == 0 && indexer_depth == 0;
 
-    for c in line.chars() {
         //? String handling
         if let Some(delimiter) = in_string {
             if next_character_escaped {
@@ -44,16 +195,39 @@ pub fn get_symbols_from_line(line: &str) -> Result<Vec<Symbol>, String> {
                 continue;
             }
 
+            if c == STRING_ESCAPE_CHAR {
+                next_character_escaped = true;
+                buffer.push(c);
+                continue;
+            }
+
             if delimiter == c {
+                //? Check escape sequences here where the column of a bad one is known
+                let contents_start = string_start + 1;
+                if let Err(e) = format_escape_codes(&buffer[string_buffer_start + 1..]) {
+                    return Err(SpanError::new(
+                        e.description,
+                        Span::new(e.span.start + contents_start, e.span.end + contents_start),
+                    ));
+                }
                 buffer.push(c);
                 in_string = None;
-                process_buffer(&mut buffer, &mut symbol_line)?;
+                //? Strings in brackets are processed with the rest of the bracket's contents
+                if at_top_level {
+                    process_buffer(&mut buffer, buffer_start, &mut symbol_line, &mut spans)?;
+                }
                 continue;
             }
 
             buffer.push(c);
             continue;
         } else if STRING_DELIMITER == c || CHAR_DELIMITER == c {
+            if at_top_level {
+                process_buffer(&mut buffer, buffer_start, &mut symbol_line, &mut spans)?;
+                buffer_start = column;
+            }
+            string_start = column;
+            string_buffer_start = buffer.len();
             buffer.push(c);
             in_string = Some(c);
             continue;
@@ -64,11 +238,42 @@ pub fn get_symbols_from_line(line: &str) -> Result<Vec<Symbol>, String> {
             break;
         }
 
-        if bracket_depth == 0 && indexer_depth == 0 {
+        if at_top_level {
+            //? Type parameters are part of the type's symbol e.g. ptr<opt<int>>
+            if c == '<' && is_type_parameter_start(&buffer, type_parameter_depth) {
+                type_parameter_depth += 1;
+                buffer.push(c);
+                continue;
+            }
+            if type_parameter_depth > 0 {
+                if c == TYPE_PARAMETER_CLOSE {
+                    type_parameter_depth -= 1;
+                }
+                buffer.push(c);
+                continue;
+            }
+
+            //? Operators don't need whitespace around them e.g. a+b -> a + b
+            let buffer_is_operator = buffer.starts_with(is_operator_character);
+            if is_operator_character(c) {
+                //? Sign of a float's exponent e.g. 1e-5
+                let exponent_sign = matches!(c, '-' | '+') && is_exponent_start(&buffer);
+                if !exponent_sign && !buffer_is_operator {
+                    process_buffer(&mut buffer, buffer_start, &mut symbol_line, &mut spans)?;
+                }
+                if buffer.is_empty() {
+                    buffer_start = column;
+                }
+                buffer.push(c);
+                continue;
+            } else if buffer_is_operator {
+                process_buffer(&mut buffer, buffer_start, &mut symbol_line, &mut spans)?;
+            }
+
             match c {
                 //? Process buffer, ignore c
                 ' ' => {
-                    process_buffer(&mut buffer, &mut symbol_line)?;
+                    process_buffer(&mut buffer, buffer_start, &mut symbol_line, &mut spans)?;
                     continue;
                 }
                 //? Process buffer, then process c
@@ -77,7 +282,7 @@ pub fn get_symbols_from_line(line: &str) -> Result<Vec<Symbol>, String> {
                 | OPEN_INDEXER_CHARACTER
                 | CLOSE_INDEXER_CHARACTER
                 | LIST_SEPARATOR_CHARACTER => {
-                    process_buffer(&mut buffer, &mut symbol_line)?;
+                    process_buffer(&mut buffer, buffer_start, &mut symbol_line, &mut spans)?;
                 }
                 _ => {}
             };
@@ -90,7 +295,9 @@ pub fn get_symbols_from_line(line: &str) -> Result<Vec<Symbol>, String> {
 
         //? Start bracket
         if c == OPEN_BRACKET_CHARACTER {
-            if bracket_depth != 0 {
+            if at_top_level {
+                buffer_start = column;
+            } else {
                 buffer.push(c);
             }
             bracket_depth += 1;
@@ -99,7 +306,9 @@ pub fn get_symbols_from_line(line: &str) -> Result<Vec<Symbol>, String> {
 
         //? Start bracket
         if c == OPEN_INDEXER_CHARACTER {
-            if indexer_depth != 0 {
+            if at_top_level {
+                buffer_start = column;
+            } else {
                 buffer.push(c);
             }
             indexer_depth += 1;
@@ -111,16 +320,23 @@ pub fn get_symbols_from_line(line: &str) -> Result<Vec<Symbol>, String> {
             bracket_depth -= 1;
 
             match bracket_depth {
-                0 => {
-                    symbol_line.push(get_bracketed_symbols_type(get_symbols_from_line(
-                        buffer.as_str(),
-                    )?));
+                0 if indexer_depth == 0 => {
+                    let (symbols, children) =
+                        get_symbols_and_spans_from_line(buffer.as_str(), buffer_start + 1)?;
+                    let (symbol, children) = get_bracketed_symbols_type(symbols, children);
+                    push_symbol(
+                        symbol,
+                        SymbolSpan::new(Span::new(buffer_start, column + 1), children),
+                        &mut symbol_line,
+                        &mut spans,
+                    );
                     buffer.clear();
                 }
                 i32::MIN..=-1 => {
-                    return Err(
+                    return Err(SpanError::new(
                         "Closing bracket found with no corresponding opening bracket".to_string(),
-                    );
+                        Span::new(column, column + 1),
+                    ));
                 }
                 _ => {
                     buffer.push(c);
@@ -135,23 +351,36 @@ pub fn get_symbols_from_line(line: &str) -> Result<Vec<Symbol>, String> {
             indexer_depth -= 1;
 
             match indexer_depth {
-                0 => {
+                0 if bracket_depth == 0 => {
                     if symbol_line.is_empty() {
-                        return Err("Indexers must be applied to something".to_string());
+                        return Err(SpanError::new(
+                            "Indexers must be applied to something".to_string(),
+                            Span::new(buffer_start, column + 1),
+                        ));
                     }
 
                     let applied_to = symbol_line.pop().unwrap();
-                    let index = get_symbols_from_line(buffer.as_str())?;
+                    let applied_to_span = spans.pop().unwrap();
+                    let (index, index_spans) =
+                        get_symbols_and_spans_from_line(buffer.as_str(), buffer_start + 1)?;
 
                     symbol_line.push(Symbol::Indexer(Box::new(applied_to), index));
+                    spans.push(SymbolSpan::new(
+                        Span::new(applied_to_span.span.start, column + 1),
+                        vec![
+                            applied_to_span,
+                            SymbolSpan::new(Span::new(buffer_start + 1, column), index_spans),
+                        ],
+                    ));
 
                     buffer.clear();
                 }
                 i32::MIN..=-1 => {
-                    return Err(
+                    return Err(SpanError::new(
                         "Closing indexing bracket found with no corresponding opening bracket"
                             .to_string(),
-                    );
+                        Span::new(column, column + 1),
+                    ));
                 }
                 _ => {
                     buffer.push(c);
@@ -161,23 +390,34 @@ pub fn get_symbols_from_line(line: &str) -> Result<Vec<Symbol>, String> {
             continue;
         }
 
+        if at_top_level && buffer.is_empty() {
+            buffer_start = column;
+        }
         buffer.push(c);
     }
 
+    let line_end = offset + line.chars().count();
+
     if in_string.is_some() {
-        return Err("Unclosed string".to_string());
+        return Err(SpanError::new(
+            "Unclosed string".to_string(),
+            Span::new(buffer_start, line_end),
+        ));
     }
 
     if bracket_depth != 0 {
-        return Err("Unclosed brackets".to_string());
+        return Err(SpanError::new(
+            "Unclosed brackets".to_string(),
+            Span::new(buffer_start, line_end),
+        ));
     }
 
     //? Push remaining data
     if !buffer.is_empty() {
-        process_buffer(&mut buffer, &mut symbol_line)?;
+        process_buffer(&mut buffer, buffer_start, &mut symbol_line, &mut spans)?;
     }
 
-    Ok(symbol_line)
+    Ok((symbol_line, spans))
 }
 
 pub struct Line {
@@ -185,6 +425,10 @@ pub struct Line {
     pub line_index: usize,
     pub indentation: usize,
     pub symbols: Vec<Symbol>,
+    /// The columns covered by each symbol in `symbols`
+    pub spans: Vec<SymbolSpan>,
+    /// Maps `spans` back to the source if the line was joined from several source lines
+    pub line_map: LineMap,
 }
 
 impl Line {
@@ -193,43 +437,148 @@ impl Line {
         line_index: usize,
         indentation: usize,
         symbols: Vec<Symbol>,
+        spans: Vec<SymbolSpan>,
+        line_map: LineMap,
     ) -> Line {
         Line {
             file_name_index,
             line_index,
             indentation,
             symbols,
+            spans,
+            line_map,
         }
     }
+
+    /// Returns the columns covered by a symbol, following `path` through nested symbols (see
+    /// `SymbolSpan::get_nested`)
+    pub fn get_span(&self, path: &[usize]) -> Option<Span> {
+        SymbolSpan::get_nested(&self.spans, path).map(|s| s.span)
+    }
+
+    /// Returns the index of the source line containing `span` and the columns it covers in that
+    /// line, for lines joined from several source lines
+    pub fn locate(&self, span: Span) -> (usize, Span) {
+        let (offset, span) = self.line_map.locate(span);
+        (self.line_index + offset, span)
+    }
+}
+
+/// `##` comments documenting the declaration that follows them
+pub struct DocComment {
+    /// The index of the declaration in `SymbolData::lines`
+    pub line_index: usize,
+    /// The comment's lines without the leading `##`
+    pub text: String,
+}
+
+/// The type given to a variable declared with `let`, recorded during compilation so tools can show
+/// it e.g. when hovering over the name
+pub struct InferredType {
+    /// The index of the declaration in `SymbolData::lines`
+    pub line_index: usize,
+    pub name: Vec<String>,
+    pub type_symbol: TypeSymbol,
 }
 
 pub struct SymbolData {
     file_names: Vec<String>,
+    /// The canonical path of each file, used to identify files imported more than once
+    file_paths: Vec<PathBuf>,
+    /// Directories searched for imported files not found relative to the importing file
+    search_paths: Vec<PathBuf>,
+    /// The source code of each file, kept for diagnostics
+    file_sources: Vec<Vec<String>>,
+    /// The namespace and imports of each file
+    pub modules: Vec<Module>,
     pub lines: Vec<Line>,
+    /// Documentation of function, class and enum declarations
+    pub doc_comments: Vec<DocComment>,
+    /// Types of variables declared with `let`. Empty until the lines are compiled
+    pub inferred_types: Vec<InferredType>,
 }
 
 impl SymbolData {
     pub fn new() -> SymbolData {
         SymbolData {
             file_names: Vec::new(),
+            file_paths: Vec::new(),
+            search_paths: Vec::new(),
+            file_sources: Vec::new(),
+            modules: Vec::new(),
             lines: Vec::new(),
+            doc_comments: Vec::new(),
+            inferred_types: Vec::new(),
         }
     }
 
-    pub fn add_file_name(&mut self, file_name: String) -> usize {
+    pub fn add_file(
+        &mut self,
+        file_name: String,
+        source: &str,
+        namespace: Option<String>,
+    ) -> usize {
+        self.file_paths.push(get_canonical_path(&file_name));
         self.file_names.push(file_name);
+        self.file_sources
+            .push(source.lines().map(|l| l.to_string()).collect());
+        self.modules.push(Module::new(namespace));
         self.file_names.len() - 1
     }
 
+    /// Sets the directories searched for imported files (see `file_util::get_search_paths`)
+    pub fn set_search_paths(&mut self, search_paths: Vec<PathBuf>) {
+        self.search_paths = search_paths;
+    }
+
+    /// Returns the index of the file with the canonical path `path` if it has been loaded
+    pub fn find_file(&self, path: &Path) -> Option<usize> {
+        self.file_paths.iter().position(|p| p == path)
+    }
+
+    /// Returns the doc comment of the declaration at `line_index` in `lines`
+    pub fn get_doc_comment(&self, line_index: usize) -> Option<&str> {
+        self.doc_comments
+            .iter()
+            .find(|d| d.line_index == line_index)
+            .map(|d| d.text.as_str())
+    }
+
+    /// Returns each file and the files it imports, in the order they were loaded
+    pub fn get_import_graph(&self) -> Vec<(&str, Vec<&str>)> {
+        self.modules
+            .iter()
+            .enumerate()
+            .map(|(i, module)| {
+                (
+                    self.file_names[i].as_str(),
+                    module
+                        .imports
+                        .iter()
+                        .map(|(_, imported)| self.file_names[*imported].as_str())
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
     pub fn add_line(
         &mut self,
         file_name_index: usize,
         line_index: usize,
         indentation: usize,
         line: Vec<Symbol>,
+        spans: Vec<SymbolSpan>,
+        line_map: LineMap,
     ) {
-        self.lines
-            .push(Line::new(file_name_index, line_index, indentation, line))
+        self.lines.push(Line::new(
+            file_name_index,
+            line_index,
+            indentation,
+            line,
+            spans,
+            line_map,
+        ))
     }
 
     pub fn get_error_path(&self, line_index: usize) -> String {
@@ -239,19 +588,146 @@ impl SymbolData {
             self.lines[line_index].line_index + 1
         )
     }
+
+    /// Creates a label pointing at a symbol in a line, following `path` through nested symbols.
+    /// An empty `path` labels the whole line
+    pub fn get_label(&self, line_index: usize, path: &[usize], message: String) -> Label {
+        let span = self.lines[line_index].get_span(path);
+        self.get_span_label(line_index, span, message)
+    }
+
+    /// Creates a label pointing at columns of a line (as in its `spans`), or the whole line if
+    /// `span` is `None`
+    pub fn get_span_label(&self, line_index: usize, span: Option<Span>, message: String) -> Label {
+        let line = &self.lines[line_index];
+        let (source_line, span) = match span {
+            Some(span) => {
+                let (source_line, span) = line.locate(span);
+                (source_line, Some(span))
+            }
+            None => (line.line_index, None),
+        };
+        Label::new(
+            self.file_names[line.file_name_index].clone(),
+            source_line,
+            span,
+            message,
+        )
+    }
+
+    /// Creates a label covering a range of the top level symbols in a line. Labels the whole line
+    /// if the range is empty
+    pub fn get_range_label(
+        &self,
+        line_index: usize,
+        symbols: Range<usize>,
+        message: String,
+    ) -> Label {
+        let line = &self.lines[line_index];
+        let span = if symbols.is_empty() {
+            None
+        } else {
+            line.get_span(&[symbols.start])
+                .zip(line.get_span(&[symbols.end - 1]))
+                .map(|(first, last)| first.join(&last))
+        };
+        self.get_span_label(line_index, span, message)
+    }
+}
+
+impl SourceProvider for SymbolData {
+    fn get_source_line(&self, file_name: &str, line_index: usize) -> Option<&str> {
+        let file = self.file_names.iter().position(|f| f == file_name)?;
+        self.file_sources[file].get(line_index).map(|l| l.as_str())
+    }
 }
 
 /// Takes code as an input
 ///
-/// Returns `Vec<indentation, symbol line>`
-pub fn convert_to_symbols(file_name: String, symbol_data: &mut SymbolData) -> Result<(), BoxedError> {
-    println!("Reading file '{}'", file_name);
+/// Lines containing errors are reported to `diagnostics` and skipped so that every independent
+/// error in the file is found. Stops early once the collector's error limit is reached.
+/// Only returns `Err` if the file can't be read
+pub fn convert_to_symbols(
+    file_name: String,
+    symbol_data: &mut SymbolData,
+    diagnostics: &mut DiagnosticCollector,
+) -> Result<(), BoxedError> {
+    load_module(file_name, None, None, symbol_data, diagnostics)?;
+    Ok(())
+}
+
+/// Loads a file and its imports, giving its top level names `namespace` (see `Module`).
+/// Returns the file's index. Progress is written to stderr so that stdout only contains
+/// diagnostics (see `OutputFormat`)
+fn load_module(
+    file_name: String,
+    namespace: Option<String>,
+    imported_at: Option<Label>,
+    symbol_data: &mut SymbolData,
+    diagnostics: &mut DiagnosticCollector,
+) -> Result<usize, BoxedError> {
+    eprintln!("Reading file '{}'", file_name);
     let data = load_file(&file_name)?;
 
-    println!("Processing file '{}'", file_name);
-    let file_name_index = symbol_data.add_file_name(file_name.clone());
+    eprintln!("Processing file '{}'", file_name);
+    let file_name_index = symbol_data.add_file(file_name.clone(), &data, namespace);
+    symbol_data.modules[file_name_index].imported_at = imported_at;
+
+    //? Spans are mapped back to the source line they're on in case lines were joined
+    let line_error = |message: String,
+                      code: &'static str,
+                      line_index: usize,
+                      line_map: &LineMap,
+                      span: Option<Span>| {
+        let (line_index, span) = match span {
+            Some(span) => {
+                let (offset, span) = line_map.locate(span);
+                (line_index + offset, Some(span))
+            }
+            None => (line_index, None),
+        };
+        Diagnostic::error(message)
+            .with_code(code)
+            .with_primary(Label::new(
+                file_name.clone(),
+                line_index,
+                span,
+                String::new(),
+            ))
+    };
+
+    let (code, unclosed_comment) = remove_block_comments(&data);
+    if let Some((line_index, column)) = unclosed_comment {
+        diagnostics.push(line_error(
+            "Unclosed block comment".to_string(),
+            SYMBOL_ERROR,
+            line_index,
+            &LineMap::new(),
+            Some(Span::new(
+                column,
+                column + BLOCK_COMMENT_OPEN.chars().count(),
+            )),
+        ));
+    }
+
+    let source_lines: Vec<&str> = code.lines().collect();
+    let mut doc_comment: Vec<&str> = Vec::new();
+    let mut next_line_index = 0;
+    while next_line_index < source_lines.len() {
+        //? Stop if the error limit was reached, possibly while processing an import
+        if diagnostics.is_full() {
+            break;
+        }
+
+        let line_index = next_line_index;
+        let line = source_lines[line_index];
+
+        if let Some(text) = line.trim_start().strip_prefix(DOC_COMMENT_START) {
+            doc_comment.push(text.strip_prefix(' ').unwrap_or(text).trim_end());
+            next_line_index += 1;
+            continue;
+        }
 
-    for (line_index, line) in data.lines().enumerate() {
         //? Count indentation
         let mut indentation_count: usize = 0;
         let mut indentation_char_count: usize = 0;
@@ -265,65 +741,445 @@ pub fn convert_to_symbols(file_name: String, symbol_data: &mut SymbolData) -> Re
             }
             indentation_char_count += 1;
         }
-        if indentation_count % 4 != 0 {
-            return Err(SimpleLineError::new(
+
+        //? Join lines continuing this one
+        let (statement, line_map, line_count) =
+            join_continued_lines(&source_lines[line_index..], indentation_char_count);
+        next_line_index += line_count;
+
+        //? Get symbols
+        let (mut symbols, mut spans) =
+            match get_symbols_and_spans_from_line(&statement, indentation_char_count) {
+                Err(e) => {
+                    diagnostics.push(line_error(
+                        e.description,
+                        SYMBOL_ERROR,
+                        line_index,
+                        &line_map,
+                        Some(e.span),
+                    ));
+                    doc_comment.clear();
+                    continue;
+                }
+                Ok(symbols) => symbols,
+            };
+
+        //? Lines without symbols, such as those ending a block comment, can be indented freely
+        if !symbols.is_empty() && indentation_count % 4 != 0 {
+            diagnostics.push(line_error(
                 "Indentation must be a multiple of 4 spaces or single tabs".to_string(),
+                INDENTATION_ERROR,
                 line_index,
-                file_name.clone(),
-            ).into());
+                &line_map,
+                Some(Span::new(0, indentation_char_count)),
+            ));
+            doc_comment.clear();
+            continue;
         }
 
-        //? Get symbols
-        let symbols = match get_symbols_from_line(&line[indentation_char_count..]) {
-            Err(e) => return Err(SimpleLineError::new(e, line_index, file_name.clone()).into()),
-            Ok(symbols) => symbols,
-        };
+        //? Doc comments belong to the next statement, skipping blank lines and comments
+        let mut documentation = None;
+        if !symbols.is_empty() && !doc_comment.is_empty() {
+            documentation = Some(doc_comment.join("\n"));
+            doc_comment.clear();
+        }
 
-        if !symbols.is_empty() {
-            let processed = match &symbols[0] {
-                Symbol::Keyword(Keyword::Import) => {
-                    if indentation_count != 0 {
-                        Err("Import statements cannot be indented".to_string())
-                    } else if symbols.len() != 2 {
-                        Err("Import statements must be formatted import [file name]".to_string())
-                    } else {
-                        match &symbols[1] {
-                            Symbol::Name(name) => {
-                                if name.len() < 2 || name.last().unwrap() != "why" {
-                                    Err("File extension must be .why".to_string())
-                                } else {
-                                    let name = join_file_name(name);
-
-                                    convert_to_symbols(name, symbol_data)?;
-                                    Ok(true)
-                                }
-                            }
-                            _ => Err("Import statements must be formatted import [file name]"
-                                .to_string()),
-                        }
-                    }
-                }
-                _ => Ok(false),
-            };
+        //? Exports
+        if let Some(Symbol::Keyword(Keyword::Pub)) = symbols.first() {
+            let pub_span = spans[0].span;
+            if indentation_count != 0 {
+                diagnostics.push(line_error(
+                    "Only top level declarations can be marked 'pub'".to_string(),
+                    MODULE_ERROR,
+                    line_index,
+                    &line_map,
+                    Some(pub_span),
+                ));
+                continue;
+            }
 
-            match processed {
-                Ok(true) => continue,
-                Ok(false) => {}
-                Err(e) => return Err(SimpleLineError::new(e, line_index, file_name.clone()).into()),
+            symbols.remove(0);
+            spans.remove(0);
+            let exported = get_exported_names(&symbols);
+            if exported.is_empty() {
+                diagnostics.push(line_error(
+                    "'pub' must be followed by a declaration".to_string(),
+                    MODULE_ERROR,
+                    line_index,
+                    &line_map,
+                    Some(pub_span),
+                ));
+                continue;
+            }
+            symbol_data.modules[file_name_index]
+                .exports
+                .extend(exported);
+        }
+
+        if let Some(Symbol::Keyword(Keyword::Import)) = symbols.first() {
+            let result = if indentation_count != 0 {
+                Err((
+                    "Import statements cannot be indented".to_string(),
+                    Some(spans[0].span),
+                ))
+            } else {
+                process_import(
+                    &symbols,
+                    &spans,
+                    line_index,
+                    &line_map,
+                    file_name_index,
+                    symbol_data,
+                    diagnostics,
+                )
             };
+            if let Err((e, span)) = result {
+                diagnostics.push(line_error(e, IMPORT_ERROR, line_index, &line_map, span));
+            }
+            continue;
+        }
+
+        if let Some(text) = documentation {
+            if get_block_declaration(&symbols).is_some() {
+                symbol_data.doc_comments.push(DocComment {
+                    line_index: symbol_data.lines.len(),
+                    text,
+                });
+            }
+        }
+
+        symbol_data.add_line(
+            file_name_index,
+            line_index,
+            indentation_count / 4,
+            symbols,
+            spans,
+            line_map,
+        );
+    }
+
+    if !diagnostics.is_full() {
+        for diagnostic in resolve_names(symbol_data, file_name_index) {
+            diagnostics.push(diagnostic);
+        }
+    }
+    symbol_data.modules[file_name_index].loaded = true;
+
+    eprintln!("Finished processing '{}'", file_name);
+
+    Ok(file_name_index)
+}
+
+/// Replaces block comments (which can be nested) with spaces, keeping line breaks so that line
+/// and column numbers don't change.
+///
+/// Also returns the line and column where an unclosed block comment starts, if there is one
+fn remove_block_comments(source: &str) -> (String, Option<(usize, usize)>) {
+    let mut code = String::with_capacity(source.len());
+    let mut comment_starts: Vec<(usize, usize)> = Vec::new();
+    let mut delimiter: Option<char> = None;
+    let mut escaped = false;
+    let mut in_line_comment = false;
+    let (mut line_index, mut column) = (0, 0);
+
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        let pair = chars.peek().map(|next| format!("{}{}", c, next));
+        let pair = pair.as_deref();
+
+        if c == '\n' {
+            code.push(c);
+            delimiter = None;
+            escaped = false;
+            in_line_comment = false;
+            line_index += 1;
+            column = 0;
+            continue;
+        }
+
+        let mut width = 1;
+        if !comment_starts.is_empty() {
+            if pair == Some(BLOCK_COMMENT_OPEN) || pair == Some(BLOCK_COMMENT_CLOSE) {
+                if pair == Some(BLOCK_COMMENT_OPEN) {
+                    comment_starts.push((line_index, column));
+                } else {
+                    comment_starts.pop();
+                }
+                chars.next();
+                width = 2;
+            }
+            code.push_str(&" ".repeat(width));
+        } else if let Some(d) = delimiter {
+            if escaped {
+                escaped = false;
+            } else if c == STRING_ESCAPE_CHAR {
+                escaped = true;
+            } else if c == d {
+                delimiter = None;
+            }
+            code.push(c);
+        } else if in_line_comment {
+            code.push(c);
+        } else if pair == Some(BLOCK_COMMENT_OPEN) {
+            comment_starts.push((line_index, column));
+            chars.next();
+            width = 2;
+            code.push_str("  ");
+        } else {
+            match c {
+                COMMENT_CHARACTER => in_line_comment = true,
+                STRING_DELIMITER | CHAR_DELIMITER => delimiter = Some(c),
+                _ => {}
+            }
+            code.push(c);
+        }
+        column += width;
+    }
+
+    (code, comment_starts.first().copied())
+}
+
+/// The result of scanning a source line for what affects whether it continues onto the next
+struct LineScan {
+    /// The number of brackets opened minus the number closed
+    bracket_depth_change: isize,
+    /// The byte position of a trailing `CONTINUATION_CHARACTER`
+    continuation: Option<usize>,
+    /// The byte position where a comment starts
+    comment: Option<usize>,
+}
+
+/// Scans a line for brackets, comments and a trailing continuation, ignoring the contents of
+/// string and char literals
+fn scan_line(line: &str) -> LineScan {
+    let mut bracket_depth_change: isize = 0;
+    let mut comment = None;
+    let mut delimiter: Option<char> = None;
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        if let Some(d) = delimiter {
+            if escaped {
+                escaped = false;
+            } else if c == STRING_ESCAPE_CHAR {
+                escaped = true;
+            } else if c == d {
+                delimiter = None;
+            }
+            continue;
+        }
+
+        match c {
+            STRING_DELIMITER | CHAR_DELIMITER => delimiter = Some(c),
+            COMMENT_CHARACTER => {
+                comment = Some(i);
+                break;
+            }
+            OPEN_BRACKET_CHARACTER | OPEN_INDEXER_CHARACTER => bracket_depth_change += 1,
+            CLOSE_BRACKET_CHARACTER | CLOSE_INDEXER_CHARACTER => bracket_depth_change -= 1,
+            _ => {}
+        }
+    }
+
+    let code = line[..comment.unwrap_or(line.len())].trim_end();
+    let continuation = match delimiter {
+        None if code.ends_with(CONTINUATION_CHARACTER) => {
+            Some(code.len() - CONTINUATION_CHARACTER.len_utf8())
+        }
+        _ => None,
+    };
+
+    LineScan {
+        bracket_depth_change,
+        continuation,
+        comment,
+    }
+}
+
+/// Joins a line with the lines following it while it has unclosed brackets or ends with
+/// `CONTINUATION_CHARACTER`. `indentation` is the number of indentation characters in the first
+/// line, which aren't included in the statement.
+///
+/// Returns the statement, a map from its columns to the source lines and the number of lines used
+fn join_continued_lines(lines: &[&str], indentation: usize) -> (String, LineMap, usize) {
+    let mut statement = String::new();
+    let mut line_map = LineMap::new();
+    let mut bracket_depth: isize = 0;
+    let mut line_count = 0;
+
+    loop {
+        let line = lines[line_count];
+        let contents = if line_count == 0 {
+            &line[indentation..]
+        } else {
+            //? Continuation lines are separated by a space and can be indented freely
+            let contents = line.trim_start();
+            statement.push(' ');
+            line_map.add_line(
+                indentation + statement.chars().count(),
+                line_count,
+                line.chars().count() - contents.chars().count(),
+            );
+            contents
+        };
+        line_count += 1;
+
+        let scan = scan_line(contents);
+        bracket_depth += scan.bracket_depth_change;
+        let continues =
+            (bracket_depth > 0 || scan.continuation.is_some()) && line_count < lines.len();
+
+        if continues {
+            //? Comments would hide the lines joined after them
+            let end = scan.continuation.or(scan.comment).unwrap_or(contents.len());
+            statement += &contents[..end];
+        } else {
+            statement += contents;
+            return (statement, line_map, line_count);
         }
+    }
+}
 
-        symbol_data.add_line(file_name_index, line_index, indentation_count / 4, symbols);
+/// Handles `import [file name]` or `import [file name] as [alias]`, loading the file and adding it
+/// to the importing file's imports
+fn process_import(
+    symbols: &[Symbol],
+    spans: &[SymbolSpan],
+    line_index: usize,
+    line_map: &LineMap,
+    file_name_index: usize,
+    symbol_data: &mut SymbolData,
+    diagnostics: &mut DiagnosticCollector,
+) -> Result<(), (String, Option<Span>)> {
+    fn format_error() -> String {
+        format!(
+            "Import statements must be formatted {} [file name] or {} [file name] {} [alias]",
+            Keyword::Import.get_code_representation(),
+            Keyword::Import.get_code_representation(),
+            Keyword::As.get_code_representation()
+        )
     }
 
-    println!("Finished processing '{}'", file_name);
+    let (name, alias_index) = match symbols {
+        [_, Symbol::Name(name)] => (name, None),
+        [_, Symbol::Name(name), Symbol::Keyword(Keyword::As), Symbol::Name(_)] => (name, Some(3)),
+        _ => return Err((format_error(), None)),
+    };
+
+    if name.len() < 2 || name.last().unwrap() != "why" {
+        return Err((
+            "File extension must be .why".to_string(),
+            Some(spans[1].span),
+        ));
+    }
 
+    //? The alias defaults to the file name without its extension
+    let alias = match alias_index {
+        Some(i) => {
+            let Symbol::Name(alias) = &symbols[i] else {
+                unreachable!()
+            };
+            if alias.len() != 1 {
+                return Err((
+                    "Import aliases cannot contain separators".to_string(),
+                    Some(spans[i].span),
+                ));
+            }
+            alias[0].clone()
+        }
+        None => name[name.len() - 2].clone(),
+    };
+    let alias_span = spans[alias_index.unwrap_or(1)].span;
+    if symbol_data.modules[file_name_index]
+        .get_import(&alias)
+        .is_some()
+    {
+        return Err((
+            format!("'{}' is already used as an import alias", alias),
+            Some(alias_span),
+        ));
+    }
+
+    let import_file_name = find_import(
+        &symbol_data.file_names[file_name_index],
+        &get_import_path(name),
+        &symbol_data.search_paths,
+    )
+    .map_err(|e| (e, Some(spans[1].span)))?
+    .to_string_lossy()
+    .to_string();
+    let (import_line_offset, import_span) = line_map.locate(spans[1].span);
+    let import_location = Label::new(
+        symbol_data.file_names[file_name_index].clone(),
+        line_index + import_line_offset,
+        Some(import_span),
+        "imported here".to_string(),
+    );
+
+    //? Each file is only loaded once
+    let imported = match symbol_data.find_file(&get_canonical_path(&import_file_name)) {
+        Some(imported) if !symbol_data.modules[imported].loaded => {
+            //? Files still being loaded are those in the current chain of imports
+            let chain: Vec<usize> = (imported..symbol_data.modules.len())
+                .filter(|i| !symbol_data.modules[*i].loaded)
+                .collect();
+            let mut chain_names: Vec<&str> = chain
+                .iter()
+                .map(|i| symbol_data.file_names[*i].as_str())
+                .collect();
+            chain_names.push(&symbol_data.file_names[imported]);
+
+            let mut diagnostic = Diagnostic::error(format!(
+                "Import cycle detected: {}",
+                chain_names.join(" -> ")
+            ))
+            .with_code(IMPORT_ERROR)
+            .with_primary(Label {
+                message: String::new(),
+                ..import_location
+            });
+            for i in &chain[1..] {
+                if let Some(label) = &symbol_data.modules[*i].imported_at {
+                    diagnostic = diagnostic.with_secondary(label.clone());
+                }
+            }
+            diagnostics.push(diagnostic);
+            return Ok(());
+        }
+        Some(imported) => imported,
+        None => {
+            //? Namespaces are keyed on the file being loaded as the same import can refer to
+            //? different files from different directories e.g. `lib#2`
+            let namespace = format!(
+                "{}#{}",
+                name[..name.len() - 1].join(MODULE_SEPARATOR),
+                symbol_data.modules.len()
+            );
+            load_module(
+                import_file_name,
+                Some(namespace),
+                Some(import_location),
+                symbol_data,
+                diagnostics,
+            )
+            .map_err(|e| (e.to_string(), Some(spans[1].span)))?
+        }
+    };
+
+    symbol_data.modules[file_name_index]
+        .imports
+        .push((alias, imported));
     Ok(())
 }
 
-fn get_bracketed_symbols_type(symbols: Vec<Symbol>) -> Symbol {
+/// Converts the contents of brackets into a `BracketedSection` or, if they contain list
+/// separators, a `List`. Returns the symbol and the spans of its children
+fn get_bracketed_symbols_type(
+    symbols: Vec<Symbol>,
+    spans: Vec<SymbolSpan>,
+) -> (Symbol, Vec<SymbolSpan>) {
     if symbols.is_empty() {
-        return Symbol::List(Vec::new());
+        return (Symbol::List(Vec::new()), Vec::new());
     }
 
     let mut has_separator = false;
@@ -335,24 +1191,46 @@ fn get_bracketed_symbols_type(symbols: Vec<Symbol>) -> Symbol {
     }
 
     if !has_separator {
-        return Symbol::BracketedSection(symbols);
+        return (Symbol::BracketedSection(symbols), spans);
+    }
+
+    fn item_span(item_spans: Vec<SymbolSpan>, separator: &SymbolSpan) -> SymbolSpan {
+        let span = match (item_spans.first(), item_spans.last()) {
+            (Some(first), Some(last)) => first.span.join(&last.span),
+            //? Point empty items at the separator after them
+            _ => separator.span,
+        };
+        SymbolSpan::new(span, item_spans)
     }
 
     let mut list = Vec::new();
+    let mut list_spans = Vec::new();
     let mut item = Vec::new();
+    let mut item_spans = Vec::new();
 
-    for s in symbols {
+    for (s, span) in symbols.into_iter().zip(spans) {
         if matches!(s, Symbol::Punctuation(Punctuation::ListSeparator)) {
             list.push(item);
+            list_spans.push(item_span(item_spans, &span));
             item = Vec::new();
+            item_spans = Vec::new();
         } else {
             item.push(s);
+            item_spans.push(span);
         }
     }
 
     if !item.is_empty() {
         list.push(item);
+        list_spans.push(SymbolSpan::new(
+            item_spans
+                .first()
+                .unwrap()
+                .span
+                .join(&item_spans.last().unwrap().span),
+            item_spans,
+        ));
     }
 
-    Symbol::List(list)
+    (Symbol::List(list), list_spans)
 }
@@ -0,0 +1,122 @@
+use std::fmt::{Display, Formatter};
+
+/// The columns (in chars, from the start of the line including indentation) covered by part of a
+/// line. `end` is exclusive
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Span {
+        Span { start, end }
+    }
+
+    /// Returns a span covering both `self` and `other`
+    pub fn join(&self, other: &Span) -> Span {
+        Span::new(self.start.min(other.start), self.end.max(other.end))
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!("Column {}", self.start + 1))
+    }
+}
+
+/// Maps the columns of a statement joined from several source lines back to the source lines.
+/// Each segment is the statement column where a source line's contents start, the line's offset
+/// from the statement's first line and the column in that line where its contents start
+#[derive(PartialEq, Clone, Debug)]
+pub struct LineMap {
+    segments: Vec<(usize, usize, usize)>,
+}
+
+impl LineMap {
+    /// A map for a statement that is a single source line
+    pub fn new() -> LineMap {
+        LineMap {
+            segments: vec![(0, 0, 0)],
+        }
+    }
+
+    /// Records that the contents of the source line `line_offset` lines after the first start at
+    /// `statement_column` in the statement and `source_column` in the line
+    pub fn add_line(&mut self, statement_column: usize, line_offset: usize, source_column: usize) {
+        self.segments
+            .push((statement_column, line_offset, source_column));
+    }
+
+    /// Returns the offset of the source line containing the start of `span` from the statement's
+    /// first line and the columns of `span` in that line. Spans covering several lines are cut
+    /// off at the end of the first
+    pub fn locate(&self, span: Span) -> (usize, Span) {
+        let segment = self
+            .segments
+            .iter()
+            .rposition(|(start, _, _)| *start <= span.start)
+            .unwrap_or(0);
+        let (start, line_offset, source_column) = self.segments[segment];
+
+        //? The segments are separated by a space which isn't part of either line
+        let end = match self.segments.get(segment + 1) {
+            Some((next_start, _, _)) => span.end.min(next_start - 1).max(span.start + 1),
+            None => span.end,
+        };
+
+        (
+            line_offset,
+            Span::new(
+                span.start - start + source_column,
+                end - start + source_column,
+            ),
+        )
+    }
+}
+
+/// The span of a `Symbol` and the spans of the symbols nested inside it, mirroring the symbol's
+/// structure:
+/// * `BracketedSection` - one child per symbol
+/// * `List` - one child per item, each with one child per symbol in the item
+/// * `Indexer` - the symbol indexed followed by the index, with one child per symbol in the index
+#[derive(PartialEq, Clone, Debug)]
+pub struct SymbolSpan {
+    pub span: Span,
+    pub children: Vec<SymbolSpan>,
+}
+
+impl SymbolSpan {
+    pub fn new(span: Span, children: Vec<SymbolSpan>) -> SymbolSpan {
+        SymbolSpan { span, children }
+    }
+
+    pub fn leaf(start: usize, end: usize) -> SymbolSpan {
+        SymbolSpan::new(Span::new(start, end), Vec::new())
+    }
+
+    /// Follows `path` through nested symbols e.g. `[2, 0]` is the first symbol inside the third
+    /// symbol. Returns `None` if the path doesn't exist
+    pub fn get_nested<'a>(spans: &'a [SymbolSpan], path: &[usize]) -> Option<&'a SymbolSpan> {
+        let (first, rest) = path.split_first()?;
+        let span = spans.get(*first)?;
+        if rest.is_empty() {
+            Some(span)
+        } else {
+            SymbolSpan::get_nested(&span.children, rest)
+        }
+    }
+}
+
+/// An error that refers to specific columns of a line
+#[derive(Debug)]
+pub struct SpanError {
+    pub description: String,
+    pub span: Span,
+}
+
+impl SpanError {
+    pub fn new(description: String, span: Span) -> SpanError {
+        SpanError { description, span }
+    }
+}
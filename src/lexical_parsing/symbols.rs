@@ -13,13 +13,17 @@ use strum::IntoEnumIterator;
 
 pub use literals::Literal;
 use literals::LiteralSymbolHandler;
+pub use literals::format_escape_codes;
 pub use literals::CHAR_DELIMITER;
+pub use literals::HEX_PREFIX;
 pub use literals::STRING_DELIMITER;
+pub use literals::STRING_ESCAPE_CHAR;
 
 pub use operators::Operator;
 use operators::OperatorSymbolHandler;
 
 pub use types::TypeSymbol;
+pub use types::{OPTIONAL_OPEN, TYPED_POINTER_OPEN, TYPE_PARAMETER_CLOSE};
 use types::TypeSymbolHandler;
 
 pub use blocks::Block;
@@ -135,6 +139,11 @@ pub fn try_bracketed_into_parameters(bracketed: &Symbol) -> Result<Literal, Stri
 pub const ALLOWED_CHARS_IN_NAME: &str = "abcdefghijklmnopqrstuvwxyz_";
 pub const NAME_SEPARATOR: char = '.';
 pub const CLASS_SELF_NAME: &str = "self";
+/// Hidden parameter pointing to where a function writes its return value. `return` is a keyword so
+/// this can't clash with a variable
+pub const RETURN_VALUE_NAME: &str = "return";
+/// Hidden variable holding the position a function jumps back to. Names can't contain spaces
+pub const RETURN_ADDRESS_NAME: &str = "return address";
 pub const FORBIDDEN_NAMES: [&str; 1] = [CLASS_SELF_NAME];
 
 struct AllSymbolHandler {}
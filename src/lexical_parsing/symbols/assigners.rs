@@ -10,6 +10,12 @@ pub enum Assigner {
     SubtractionSetter,
     ProductSetter,
     DivisionSetter,
+    ModuloSetter,
+    AndSetter,
+    OrSetter,
+    XorSetter,
+    LeftShiftSetter,
+    RightShiftSetter,
 }
 
 impl Assigner {
@@ -22,6 +28,12 @@ impl Assigner {
             Assigner::SubtractionSetter => Operator::Subtract,
             Assigner::ProductSetter => Operator::Product,
             Assigner::DivisionSetter => Operator::Divide,
+            Assigner::ModuloSetter => Operator::Modulo,
+            Assigner::AndSetter => Operator::And,
+            Assigner::OrSetter => Operator::Or,
+            Assigner::XorSetter => Operator::Xor,
+            Assigner::LeftShiftSetter => Operator::LeftShift,
+            Assigner::RightShiftSetter => Operator::RightShift,
         };
 
         vec![lhs, Symbol::Operator(equivalent), BracketedSection(rhs)]
@@ -38,6 +50,12 @@ impl SymbolHandler for AssignerSymbolHandler {
             "-=" => Some(Symbol::Assigner(Assigner::SubtractionSetter)),
             "*=" => Some(Symbol::Assigner(Assigner::ProductSetter)),
             "/=" => Some(Symbol::Assigner(Assigner::DivisionSetter)),
+            "%=" => Some(Symbol::Assigner(Assigner::ModuloSetter)),
+            "&=" => Some(Symbol::Assigner(Assigner::AndSetter)),
+            "|=" => Some(Symbol::Assigner(Assigner::OrSetter)),
+            "^=" => Some(Symbol::Assigner(Assigner::XorSetter)),
+            "<<=" => Some(Symbol::Assigner(Assigner::LeftShiftSetter)),
+            ">>=" => Some(Symbol::Assigner(Assigner::RightShiftSetter)),
             _ => None,
         })
     }
@@ -10,6 +10,9 @@ pub enum Block {
     Else,
     Function,
     Class,
+    Enum,
+    Match,
+    Case,
     BaseBlock,
 }
 
@@ -25,6 +28,9 @@ impl Block {
             Block::Else => "else",
             Block::Function => "fn",
             Block::Class => "class",
+            Block::Enum => "enum",
+            Block::Match => "match",
+            Block::Case => "case",
             Block::BaseBlock => "block",
         }
     }
@@ -40,6 +46,9 @@ impl SymbolHandler for BlockSymbolHandler {
             "else" => Some(Symbol::Block(Block::Else)),
             "fn" => Some(Symbol::Block(Block::Function)),
             "class" => Some(Symbol::Block(Block::Class)),
+            "enum" => Some(Symbol::Block(Block::Enum)),
+            "match" => Some(Symbol::Block(Block::Match)),
+            "case" => Some(Symbol::Block(Block::Case)),
             "block" => Some(Symbol::Block(Block::BaseBlock)),
             _ => None,
         })
@@ -5,11 +5,15 @@ use super::SymbolHandler;
 pub enum Keyword {
     Break,
     Continue,
+    Return,
     Dump,
     ViewMemory,
     ViewMemoryDecimal,
     As,
     Import,
+    Let,
+    Const,
+    Pub,
 }
 
 pub struct KeywordSymbolHandler {}
@@ -21,11 +25,15 @@ impl Keyword {
         match self {
             Keyword::Break => "break",
             Keyword::Continue => "continue",
+            Keyword::Return => "return",
             Keyword::Dump => "dump",
             Keyword::ViewMemory => "viewmem",
             Keyword::ViewMemoryDecimal => "viewmemdec",
             Keyword::As => "as",
             Keyword::Import => "import",
+            Keyword::Let => "let",
+            Keyword::Const => "const",
+            Keyword::Pub => "pub",
         }
     }
 }
@@ -36,11 +44,15 @@ impl SymbolHandler for KeywordSymbolHandler {
         Ok(match string {
             "break" => Some(Symbol::Keyword(Keyword::Break)),
             "continue" => Some(Symbol::Keyword(Keyword::Continue)),
+            "return" => Some(Symbol::Keyword(Keyword::Return)),
             "dump" => Some(Symbol::Keyword(Keyword::Dump)),
             "viewmem" => Some(Symbol::Keyword(Keyword::ViewMemory)),
             "viewmemdec" => Some(Symbol::Keyword(Keyword::ViewMemoryDecimal)),
             "as" => Some(Symbol::Keyword(Keyword::As)),
             "import" => Some(Symbol::Keyword(Keyword::Import)),
+            "let" => Some(Symbol::Keyword(Keyword::Let)),
+            "const" => Some(Symbol::Keyword(Keyword::Const)),
+            "pub" => Some(Symbol::Keyword(Keyword::Pub)),
             _ => None,
         })
     }
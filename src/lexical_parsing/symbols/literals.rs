@@ -1,11 +1,16 @@
 use super::{Symbol, TypeSymbol};
 use super::SymbolHandler;
+use super::types::TypeSymbolHandler;
+use crate::lexical_parsing::span::{Span, SpanError};
 
 #[derive(PartialEq, Clone, strum_macros::Display, Debug)]
 pub enum Literal {
     String(String),
     Char(char),
     Int(i128),
+    /// An integer with a type suffix e.g. `10u8`
+    TypedInt(i128, TypeSymbol),
+    Float(f64),
     Bool(bool),
     ParameterList(Vec<(TypeSymbol, String)>),
     None,
@@ -18,36 +23,165 @@ pub const CHAR_DELIMITER: char = '\'';
 
 pub const STRING_ESCAPE_CHAR: char = '\\';
 
-const ESCAPE_CODES: [(char, char); 5] = [
+pub const HEX_PREFIX: &str = "0x";
+pub const BINARY_PREFIX: &str = "0b";
+pub const OCTAL_PREFIX: &str = "0o";
+/// Can be placed between digits for readability e.g. `1_000_000`
+pub const DIGIT_SEPARATOR: char = '_';
+
+const ESCAPE_CODES: [(char, char); 7] = [
     ('n', '\n'),
+    ('t', '\t'),
+    ('r', '\r'),
     ('\\', '\\'),
     ('0', '\0'),
     ('"', '"'),
     ('\'', '\''),
 ];
+/// Followed by two hexadecimal digits e.g. `\x41`
+const BYTE_ESCAPE: char = 'x';
+/// Followed by 1 to 6 hexadecimal digits in braces e.g. `\u{1F600}`
+const UNICODE_ESCAPE: char = 'u';
+const MAX_UNICODE_ESCAPE_DIGITS: usize = 6;
 
-/// Takes an input string and replaces escape codes with their corresponding values
-fn format_escape_codes(input: String) -> String {
-    let mut output = String::new();
-    let mut next = false;
-    'char_loop: for c in input.chars() {
-        if next {
-            next = false;
-            for code in ESCAPE_CODES {
-                if c == code.0 {
-                    output.push(code.1);
-                    continue 'char_loop;
-                }
-            }
+/// Decodes the escape sequence after a `\` at the start of `input`. Returns the character and
+/// the number of chars of `input` used, or an error and the number of chars it covers
+fn get_escaped_char(input: &str) -> Result<(char, usize), (String, usize)> {
+    let mut chars = input.chars();
+    let Some(code) = chars.next() else {
+        return Err(("Expected an escape sequence after '\\'".to_string(), 0));
+    };
+
+    if code == BYTE_ESCAPE {
+        let digits: String = chars.take(2).collect();
+        let length = 1 + digits.chars().count();
+        if digits.len() != 2 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err((
+                format!(
+                    "'\\{}' must be followed by two hexadecimal digits e.g. \\x41",
+                    BYTE_ESCAPE
+                ),
+                length,
+            ));
+        }
+        let value = u8::from_str_radix(&digits, 16).unwrap();
+        if !value.is_ascii() {
+            return Err((
+                format!("'\\x{}' is out of range - must be at most \\x7F", digits),
+                length,
+            ));
+        }
+        return Ok((value as char, length));
+    }
+
+    if code == UNICODE_ESCAPE {
+        let format_error = format!(
+            "Unicode escapes must be written \\{}{{...}} with 1 to {} hexadecimal digits",
+            UNICODE_ESCAPE, MAX_UNICODE_ESCAPE_DIGITS
+        );
+        let rest = chars.as_str();
+        let Some(close) = rest.strip_prefix('{').and_then(|r| r.find('}')) else {
+            return Err((format_error, 1));
+        };
+        let digits = &rest[1..close + 1];
+        let length = 3 + digits.chars().count();
+        if digits.is_empty()
+            || digits.len() > MAX_UNICODE_ESCAPE_DIGITS
+            || !digits.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return Err((format_error, length));
         }
+        return match char::from_u32(u32::from_str_radix(digits, 16).unwrap()) {
+            Some(c) => Ok((c, length)),
+            None => Err((
+                format!("'\\u{{{}}}' is not a valid unicode character", digits),
+                length,
+            )),
+        };
+    }
 
-        if c == '\\' && !next {
-            next = true;
-        } else {
+    match ESCAPE_CODES.iter().find(|(c, _)| *c == code) {
+        Some((_, value)) => Ok((*value, 1)),
+        None => Err((format!("Unknown escape sequence '\\{}'", code), 1)),
+    }
+}
+
+/// Takes the contents of a string or char literal and replaces escape codes with their
+/// corresponding values. Errors cover the columns of the escape sequence, counted from the start
+/// of `input`
+pub fn format_escape_codes(input: &str) -> Result<String, SpanError> {
+    let mut output = String::new();
+    let mut chars = input.char_indices().enumerate();
+    while let Some((column, (index, c))) = chars.next() {
+        if c != STRING_ESCAPE_CHAR {
             output.push(c);
+            continue;
         }
+
+        match get_escaped_char(&input[index + c.len_utf8()..]) {
+            Ok((escaped, length)) => {
+                output.push(escaped);
+                chars.nth(length - 1);
+            }
+            Err((description, length)) => {
+                return Err(SpanError::new(
+                    description,
+                    Span::new(column, column + 1 + length),
+                ))
+            }
+        }
+    }
+    Ok(output)
+}
+
+/// Returns the radix and name of a number literal's prefix e.g. `0x`
+fn get_radix(string: &str) -> Option<(&str, u32, &str)> {
+    [
+        (HEX_PREFIX, 16, "hexadecimal"),
+        (BINARY_PREFIX, 2, "binary"),
+        (OCTAL_PREFIX, 8, "octal"),
+    ]
+    .into_iter()
+    .find_map(|(prefix, radix, name)| string.strip_prefix(prefix).map(|d| (d, radix, name)))
+}
+
+/// Parses integer literals e.g. `255`, `0xFF`, `0b1111_1111`, `255u8`. `string` must start with
+/// a digit
+fn get_int_literal(string: &str) -> Result<Literal, String> {
+    let (digits, radix, radix_name) = get_radix(string).unwrap_or((string, 10, "decimal"));
+
+    //? The suffix starts at the first character that can't be part of the number
+    let suffix_start = digits
+        .find(|c: char| !c.is_digit(radix) && c != DIGIT_SEPARATOR)
+        .unwrap_or(digits.len());
+    let (digits, suffix) = digits.split_at(suffix_start);
+
+    //? Digits too large for the radix e.g. 0b102
+    if let Some(c) = suffix.chars().next().filter(char::is_ascii_digit) {
+        return Err(format!(
+            "Invalid digit '{}' in {} literal '{}'",
+            c, radix_name, string
+        ));
+    }
+
+    let digits: String = digits.chars().filter(|c| *c != DIGIT_SEPARATOR).collect();
+    if digits.is_empty() {
+        return Err(format!("Integer literal '{}' has no digits", string));
+    }
+    let Ok(value) = i128::from_str_radix(&digits, radix) else {
+        return Err(format!("Integer literal '{}' is too large", string));
+    };
+
+    if suffix.is_empty() {
+        return Ok(Literal::Int(value));
+    }
+    match TypeSymbolHandler::get_raw_symbol(suffix) {
+        Some(t) if t.get_integer_format().is_some() => Ok(Literal::TypedInt(value, t)),
+        _ => Err(format!(
+            "Invalid suffix '{}' for integer literal '{}' - expected an integer type e.g. u8, i64",
+            suffix, string
+        )),
     }
-    output
 }
 
 impl SymbolHandler for LiteralSymbolHandler {
@@ -70,26 +204,46 @@ impl SymbolHandler for LiteralSymbolHandler {
                 && (STRING_DELIMITER == first_char || CHAR_DELIMITER == first_char)
                 && string.chars().last().unwrap() == first_char
             {
-                let formatted_string = format_escape_codes(string[1..string.len() - 1].to_string());
+                let formatted_string =
+                    format_escape_codes(&string[1..string.len() - 1]).map_err(|e| e.description)?;
 
                 if first_char == CHAR_DELIMITER {
-                    if formatted_string.len() != 1 {
-                        return Err("Char literals cannot contain multiple chars".to_string());
+                    let mut chars = formatted_string.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => Some(Symbol::Literal(Literal::Char(c))),
+                        _ => {
+                            return Err(format!(
+                                "Char literals must contain a single char, found {}",
+                                formatted_string.chars().count()
+                            ))
+                        }
                     }
+                } else {
+                    Some(Symbol::Literal(Literal::String(formatted_string)))
                 }
-                Some(Symbol::Literal(Literal::String(formatted_string)));
+            } else {
+                None
             }
-            None
         };
 
         if result.is_some() {
             return Ok(result);
         }
 
-        return Ok(match string.parse::<i128>() {
-            Ok(ok) => Some(Symbol::Literal(Literal::Int(ok))),
-            Err(_) => None,
-        });
+        if !string.starts_with(|c: char| c.is_ascii_digit()) {
+            return Ok(None);
+        }
+
+        //? Only decimal / exponent forms e.g. 1.5, 2e10 - parse also accepts words like 'inf'
+        if get_radix(string).is_none() && string.contains(['.', 'e', 'E']) {
+            let digits: String = string.chars().filter(|c| *c != DIGIT_SEPARATOR).collect();
+            return match digits.parse::<f64>() {
+                Ok(ok) => Ok(Some(Symbol::Literal(Literal::Float(ok)))),
+                Err(_) => Err(format!("Invalid float literal '{}'", string)),
+            };
+        }
+
+        get_int_literal(string).map(|literal| Some(Symbol::Literal(literal)))
     }
 }
 
@@ -104,3 +258,146 @@ impl SymbolHandler for LiteralSymbolHandler {
 //         }
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(string: &str) -> Result<Option<Symbol>, String> {
+        LiteralSymbolHandler::get_symbol(string)
+    }
+
+    fn int(value: i128) -> Result<Option<Symbol>, String> {
+        Ok(Some(Symbol::Literal(Literal::Int(value))))
+    }
+
+    #[test]
+    fn radix_prefixes() {
+        assert_eq!(parse("255"), int(255));
+        assert_eq!(parse("0xFF"), int(255));
+        assert_eq!(parse("0xff"), int(255));
+        assert_eq!(parse("0b1111"), int(15));
+        assert_eq!(parse("0o17"), int(15));
+    }
+
+    #[test]
+    fn digit_separators() {
+        assert_eq!(parse("1_000_000"), int(1_000_000));
+        assert_eq!(parse("0b1111_0000"), int(240));
+        assert_eq!(
+            parse("1_000.5"),
+            Ok(Some(Symbol::Literal(Literal::Float(1000.5))))
+        );
+    }
+
+    #[test]
+    fn type_suffixes() {
+        assert_eq!(
+            parse("10u8"),
+            Ok(Some(Symbol::Literal(Literal::TypedInt(10, TypeSymbol::U8))))
+        );
+        assert_eq!(
+            parse("0xFF_u8"),
+            Ok(Some(Symbol::Literal(Literal::TypedInt(
+                255,
+                TypeSymbol::U8
+            ))))
+        );
+    }
+
+    #[test]
+    fn invalid_int_literals() {
+        assert_eq!(
+            parse("0b102"),
+            Err("Invalid digit '2' in binary literal '0b102'".to_string())
+        );
+        assert_eq!(
+            parse("0x"),
+            Err("Integer literal '0x' has no digits".to_string())
+        );
+        assert_eq!(
+            parse("1abc"),
+            Err(
+                "Invalid suffix 'abc' for integer literal '1abc' - expected an integer type e.g. u8, i64"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            parse("10u9"),
+            Err(
+                "Invalid suffix 'u9' for integer literal '10u9' - expected an integer type e.g. u8, i64"
+                    .to_string()
+            )
+        );
+        assert!(parse("0x1_0000_0000_0000_0000_0000_0000_0000_0000").is_err());
+    }
+
+    fn string(value: &str) -> Result<Option<Symbol>, String> {
+        Ok(Some(Symbol::Literal(Literal::String(value.to_string()))))
+    }
+
+    fn char(value: char) -> Result<Option<Symbol>, String> {
+        Ok(Some(Symbol::Literal(Literal::Char(value))))
+    }
+
+    #[test]
+    fn escape_sequences() {
+        assert_eq!(parse(r#""a\tb\r\n""#), string("a\tb\r\n"));
+        assert_eq!(parse(r#""\\ \" \' \0""#), string("\\ \" ' \0"));
+        assert_eq!(parse(r#""\x41\x7F""#), string("A\x7F"));
+        assert_eq!(parse(r#""\u{e9}\u{1F600}""#), string("é😀"));
+        assert_eq!(parse(r"'\t'"), char('\t'));
+        assert_eq!(parse(r"'\u{1F600}'"), char('😀'));
+    }
+
+    #[test]
+    fn non_ascii_chars() {
+        assert_eq!(parse("'é'"), char('é'));
+        assert_eq!(parse("'😀'"), char('😀'));
+        assert_eq!(
+            parse("'ab'"),
+            Err("Char literals must contain a single char, found 2".to_string())
+        );
+    }
+
+    #[test]
+    fn invalid_escape_sequences() {
+        assert_eq!(
+            parse(r#""\q""#),
+            Err(r"Unknown escape sequence '\q'".to_string())
+        );
+        assert_eq!(
+            parse(r#""\x4""#),
+            Err(r"'\x' must be followed by two hexadecimal digits e.g. \x41".to_string())
+        );
+        assert_eq!(
+            parse(r#""\x80""#),
+            Err(r"'\x80' is out of range - must be at most \x7F".to_string())
+        );
+        assert_eq!(
+            parse(r#""\u{110000}""#),
+            Err(r"'\u{110000}' is not a valid unicode character".to_string())
+        );
+        assert!(parse(r#""\u{}""#).is_err());
+        assert!(parse(r#""\u41""#).is_err());
+    }
+
+    #[test]
+    fn escape_error_spans() {
+        //? Spans count chars, so the 'é' before the escape only moves it by one column
+        let error = format_escape_codes(r"é\q").unwrap_err();
+        assert_eq!(error.span, Span::new(1, 3));
+
+        let error = format_escape_codes(r"ab\x4").unwrap_err();
+        assert_eq!(error.span, Span::new(2, 5));
+
+        //? In a line the span is offset by the string's position
+        let error = crate::lexical_parsing::preprocessor::get_symbols_and_spans_from_line(
+            r#"print "ab\q""#,
+            0,
+        )
+        .unwrap_err();
+        assert_eq!(error.description, r"Unknown escape sequence '\q'");
+        assert_eq!(error.span, Span::new(9, 11));
+    }
+}
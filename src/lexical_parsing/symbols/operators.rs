@@ -6,8 +6,11 @@ pub enum Operator {
     Cast(TypeSymbol),
     Add,
     Subtract,
+    /// A `-` in prefix position. Produced by the lexer rather than `OperatorSymbolHandler`
+    Negate,
     Product,
     Divide,
+    Modulo,
     Greater,
     Less,
     GreaterEqual,
@@ -17,6 +20,13 @@ pub enum Operator {
     Or,
     And,
     Not,
+    Xor,
+    BitwiseNot,
+    LeftShift,
+    RightShift,
+    AddressOf,
+    Dereference,
+    Unwrap,
 }
 
 pub struct OperatorSymbolHandler {}
@@ -28,6 +38,7 @@ impl SymbolHandler for OperatorSymbolHandler {
             "-" => Some(Symbol::Operator(Operator::Subtract)),
             "*" => Some(Symbol::Operator(Operator::Product)),
             "/" => Some(Symbol::Operator(Operator::Divide)),
+            "%" => Some(Symbol::Operator(Operator::Modulo)),
             ">" => Some(Symbol::Operator(Operator::Greater)),
             "<" => Some(Symbol::Operator(Operator::Less)),
             ">=" => Some(Symbol::Operator(Operator::GreaterEqual)),
@@ -37,6 +48,13 @@ impl SymbolHandler for OperatorSymbolHandler {
             "|" => Some(Symbol::Operator(Operator::Or)),
             "&" => Some(Symbol::Operator(Operator::And)),
             "!" => Some(Symbol::Operator(Operator::Not)),
+            "^" => Some(Symbol::Operator(Operator::Xor)),
+            "~" => Some(Symbol::Operator(Operator::BitwiseNot)),
+            "<<" => Some(Symbol::Operator(Operator::LeftShift)),
+            ">>" => Some(Symbol::Operator(Operator::RightShift)),
+            "ref" => Some(Symbol::Operator(Operator::AddressOf)),
+            "deref" => Some(Symbol::Operator(Operator::Dereference)),
+            "unwrap" => Some(Symbol::Operator(Operator::Unwrap)),
             _ => None, // string => {
                        //     // TODO: Error if invalid type symbol but '<x>' is still present?
                        //     if string.len() > 2 {
@@ -1,25 +1,197 @@
+use std::fmt;
+use std::sync::Mutex;
 use crate::lexical_parsing::symbols::{Symbol, SymbolHandler};
 
-#[derive(PartialEq, Copy, Clone, strum_macros::Display, Debug, strum_macros::EnumIter)]
+#[derive(PartialEq, Copy, Clone, Debug)]
 pub enum TypeSymbol {
     Integer,
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    Float,
     Boolean,
     Character,
     // Function,
     Pointer,
+    /// `ptr<T>` - use `TypeSymbol::pointer_to` to create
+    TypedPointer(&'static TypeSymbol),
+    /// `opt<T>` - use `TypeSymbol::optional_of` to create
+    Optional(&'static TypeSymbol),
+    /// `(T, U, ...)` - use `TypeSymbol::tuple_of` to create
+    Tuple(&'static [TypeSymbol]),
+    /// A declared enum, identified by its name - use `TypeSymbol::enum_named` to create
+    Enum(&'static str),
     // Temporary(String)
 }
 
+pub const TYPED_POINTER_OPEN: &str = "ptr<";
+pub const OPTIONAL_OPEN: &str = "opt<";
+pub const TYPE_PARAMETER_CLOSE: char = '>';
+/// Shorthand for an optional e.g. `int?` is `opt<int>`
+pub const OPTIONAL_SUFFIX: char = '?';
+
+/// Returns a `'static` copy of `value` so that `TypeSymbol` can remain `Copy`. Each distinct value
+/// is only leaked once
+fn intern<T: ?Sized + PartialEq + ToOwned>(
+    interned: &Mutex<Vec<&'static T>>,
+    value: &T,
+) -> &'static T
+where
+    T::Owned: Into<Box<T>>,
+{
+    let mut interned = interned.lock().unwrap();
+    if let Some(existing) = interned.iter().find(|i| **i == value) {
+        return existing;
+    }
+
+    let new: &'static T = Box::leak(value.to_owned().into());
+    interned.push(new);
+    new
+}
+
 impl TypeSymbol {
     pub fn get_code_representation(&self) -> &str {
         match self {
             TypeSymbol::Integer => "int",
+            TypeSymbol::I8 => "i8",
+            TypeSymbol::I16 => "i16",
+            TypeSymbol::I32 => "i32",
+            TypeSymbol::I64 => "i64",
+            TypeSymbol::U8 => "u8",
+            TypeSymbol::U16 => "u16",
+            TypeSymbol::U32 => "u32",
+            TypeSymbol::U64 => "u64",
+            TypeSymbol::Float => "float",
             TypeSymbol::Boolean => "bool",
             TypeSymbol::Character => "char",
-            TypeSymbol::Pointer => "ptr",
+            TypeSymbol::Pointer | TypeSymbol::TypedPointer(_) => "ptr",
+            TypeSymbol::Optional(_) => "opt",
+            TypeSymbol::Tuple(_) => "tuple",
+            TypeSymbol::Enum(_) => "enum",
             // TypeSymbol::Temporary(type_name) => type_name
         }
     }
+
+    /// Returns the type of a pointer to `target`
+    pub fn pointer_to(target: TypeSymbol) -> TypeSymbol {
+        static TARGETS: Mutex<Vec<&'static TypeSymbol>> = Mutex::new(Vec::new());
+        TypeSymbol::TypedPointer(intern(&TARGETS, &target))
+    }
+
+    /// Returns the type of an optional `inner`
+    pub fn optional_of(inner: TypeSymbol) -> TypeSymbol {
+        static INNER: Mutex<Vec<&'static TypeSymbol>> = Mutex::new(Vec::new());
+        TypeSymbol::Optional(intern(&INNER, &inner))
+    }
+
+    /// Returns the type of a tuple of `elements`
+    pub fn tuple_of(elements: &[TypeSymbol]) -> TypeSymbol {
+        static ELEMENTS: Mutex<Vec<&'static [TypeSymbol]>> = Mutex::new(Vec::new());
+        TypeSymbol::Tuple(intern(&ELEMENTS, elements))
+    }
+
+    /// Returns the type of values of the enum declared as `name`
+    pub fn enum_named(name: &str) -> TypeSymbol {
+        static NAMES: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+        TypeSymbol::Enum(intern(&NAMES, name))
+    }
+
+    /// Returns the tuple type written as a list of types e.g. `(int, bool)`. Returns `None` if
+    /// any item isn't a single type
+    pub fn from_type_list(list: &[Vec<Symbol>]) -> Option<TypeSymbol> {
+        let mut elements = Vec::with_capacity(list.len());
+        for item in list {
+            match item.as_slice() {
+                [Symbol::Type(element)] => elements.push(*element),
+                _ => return None,
+            }
+        }
+
+        Some(TypeSymbol::tuple_of(&elements))
+    }
+
+    /// Returns the size in bytes and signedness if this is an integer type. `int` is a signed
+    /// 64-bit integer
+    pub fn get_integer_format(&self) -> Option<(usize, bool)> {
+        match self {
+            TypeSymbol::I8 => Some((1, true)),
+            TypeSymbol::I16 => Some((2, true)),
+            TypeSymbol::I32 => Some((4, true)),
+            TypeSymbol::Integer | TypeSymbol::I64 => Some((8, true)),
+            TypeSymbol::U8 => Some((1, false)),
+            TypeSymbol::U16 => Some((2, false)),
+            TypeSymbol::U32 => Some((4, false)),
+            TypeSymbol::U64 => Some((8, false)),
+            _ => None,
+        }
+    }
+
+    /// Returns the type pointed to if this is a typed pointer
+    pub fn get_pointer_target(&self) -> Option<TypeSymbol> {
+        match self {
+            TypeSymbol::TypedPointer(target) => Some(**target),
+            _ => None,
+        }
+    }
+
+    /// Returns the type held if this is an optional
+    pub fn get_optional_inner(&self) -> Option<TypeSymbol> {
+        match self {
+            TypeSymbol::Optional(inner) => Some(**inner),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the declared enum if this is an enum
+    pub fn get_enum_name(&self) -> Option<&'static str> {
+        match self {
+            TypeSymbol::Enum(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Returns the element types if this is a tuple
+    pub fn get_tuple_elements(&self) -> Option<&'static [TypeSymbol]> {
+        match self {
+            TypeSymbol::Tuple(elements) => Some(elements),
+            _ => None,
+        }
+    }
+}
+
+/// Shows the type as it would be written in code e.g. `ptr<int>` or the name of an enum
+impl fmt::Display for TypeSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeSymbol::TypedPointer(target) => {
+                write!(
+                    f,
+                    "{}{}{}",
+                    TYPED_POINTER_OPEN, target, TYPE_PARAMETER_CLOSE
+                )
+            }
+            TypeSymbol::Optional(inner) => {
+                write!(f, "{}{}{}", OPTIONAL_OPEN, inner, TYPE_PARAMETER_CLOSE)
+            }
+            TypeSymbol::Tuple(elements) => {
+                write!(f, "(")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                write!(f, ")")
+            }
+            TypeSymbol::Enum(name) => write!(f, "{}", name),
+            _ => write!(f, "{}", self.get_code_representation()),
+        }
+    }
 }
 
 // TODO: Copy this implementation patterns
@@ -27,8 +199,38 @@ pub struct TypeSymbolHandler {}
 
 impl TypeSymbolHandler {
     pub fn get_raw_symbol(string: &str) -> Option<TypeSymbol> {
+        //? Typed pointer e.g. ptr<int>
+        if let Some(target) = string
+            .strip_prefix(TYPED_POINTER_OPEN)
+            .and_then(|s| s.strip_suffix(TYPE_PARAMETER_CLOSE))
+        {
+            return TypeSymbolHandler::get_raw_symbol(target).map(TypeSymbol::pointer_to);
+        }
+
+        //? Optional e.g. opt<int>
+        if let Some(inner) = string
+            .strip_prefix(OPTIONAL_OPEN)
+            .and_then(|s| s.strip_suffix(TYPE_PARAMETER_CLOSE))
+        {
+            return TypeSymbolHandler::get_raw_symbol(inner).map(TypeSymbol::optional_of);
+        }
+
+        //? Optional shorthand e.g. int?
+        if let Some(inner) = string.strip_suffix(OPTIONAL_SUFFIX) {
+            return TypeSymbolHandler::get_raw_symbol(inner).map(TypeSymbol::optional_of);
+        }
+
         match string {
             "int" => Some(TypeSymbol::Integer),
+            "i8" => Some(TypeSymbol::I8),
+            "i16" => Some(TypeSymbol::I16),
+            "i32" => Some(TypeSymbol::I32),
+            "i64" => Some(TypeSymbol::I64),
+            "u8" => Some(TypeSymbol::U8),
+            "u16" => Some(TypeSymbol::U16),
+            "u32" => Some(TypeSymbol::U32),
+            "u64" => Some(TypeSymbol::U64),
+            "float" => Some(TypeSymbol::Float),
             "bool" => Some(TypeSymbol::Boolean),
             "char" => Some(TypeSymbol::Character),
             "ptr" => Some(TypeSymbol::Pointer),
@@ -42,3 +244,42 @@ impl SymbolHandler for TypeSymbolHandler {
         Ok(TypeSymbolHandler::get_raw_symbol(string).and_then(|s| Some(Symbol::Type(s))))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn displays_types_as_written() {
+        assert_eq!(TypeSymbol::Integer.to_string(), "int");
+        assert_eq!(
+            TypeSymbol::pointer_to(TypeSymbol::Character).to_string(),
+            "ptr<char>"
+        );
+        assert_eq!(
+            TypeSymbol::optional_of(TypeSymbol::Boolean).to_string(),
+            "opt<bool>"
+        );
+        assert_eq!(
+            TypeSymbol::tuple_of(&[TypeSymbol::Integer, TypeSymbol::Boolean]).to_string(),
+            "(int, bool)"
+        );
+        assert_eq!(TypeSymbol::enum_named("Colour").to_string(), "Colour");
+    }
+
+    #[test]
+    fn interns_equal_types_once() {
+        let a = TypeSymbol::pointer_to(TypeSymbol::Character);
+        let b = TypeSymbol::pointer_to(TypeSymbol::Character);
+        match (a, b) {
+            (TypeSymbol::TypedPointer(a), TypeSymbol::TypedPointer(b)) => {
+                assert!(std::ptr::eq(a, b))
+            }
+            _ => unreachable!(),
+        }
+        assert!(std::ptr::eq(
+            TypeSymbol::enum_named("Colour").get_enum_name().unwrap(),
+            TypeSymbol::enum_named("Colour").get_enum_name().unwrap()
+        ));
+    }
+}
@@ -0,0 +1,816 @@
+use strum::IntoEnumIterator;
+
+use crate::address::Address;
+use crate::diagnostics::{
+    Diagnostic, DiagnosticCollector, Severity, CONSTANT_CONDITION_WARNING, ELIF_AFTER_ELSE_WARNING,
+    SHADOWED_NAME_WARNING, UNREACHABLE_CODE_WARNING, UNUSED_VARIABLE_WARNING,
+};
+use crate::lexical_parsing::declarations::{get_declarations, get_parameters};
+use crate::lexical_parsing::preprocessor::SymbolData;
+use crate::lexical_parsing::symbols::{Block, Literal, Symbol};
+use crate::processing::blocks::BlockType;
+use crate::processing::reference_manager::ReferenceStack;
+
+/// Names starting with this aren't reported as unused
+pub const UNUSED_NAME_PREFIX: char = '_';
+/// Sets a lint, or every lint with `all`, to `LintLevel::Allow` e.g. `-A unused_variable`
+pub const ALLOW_FLAG: &str = "-A";
+/// Sets a lint, or every lint with `all`, to `LintLevel::Warn`
+pub const WARN_FLAG: &str = "-W";
+/// Sets a lint, or every lint with `all`, to `LintLevel::Deny`
+pub const DENY_FLAG: &str = "-D";
+
+#[derive(PartialEq, Copy, Clone, Debug, strum_macros::EnumIter)]
+pub enum Lint {
+    UnusedVariable,
+    UnreachableCode,
+    ElifAfterElse,
+    ShadowedName,
+    ConstantCondition,
+}
+
+impl Lint {
+    /// The name used to refer to the lint when configuring it
+    pub fn get_name(&self) -> &str {
+        match self {
+            Lint::UnusedVariable => "unused_variable",
+            Lint::UnreachableCode => "unreachable_code",
+            Lint::ElifAfterElse => "elif_after_else",
+            Lint::ShadowedName => "shadowed_name",
+            Lint::ConstantCondition => "constant_condition",
+        }
+    }
+
+    pub fn get_code(&self) -> &'static str {
+        match self {
+            Lint::UnusedVariable => UNUSED_VARIABLE_WARNING,
+            Lint::UnreachableCode => UNREACHABLE_CODE_WARNING,
+            Lint::ElifAfterElse => ELIF_AFTER_ELSE_WARNING,
+            Lint::ShadowedName => SHADOWED_NAME_WARNING,
+            Lint::ConstantCondition => CONSTANT_CONDITION_WARNING,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Lint> {
+        Lint::iter().find(|l| l.get_name() == name)
+    }
+}
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum LintLevel {
+    /// The lint isn't checked
+    Allow,
+    /// The lint is reported as a warning
+    Warn,
+    /// The lint is reported as an error
+    Deny,
+}
+
+impl LintLevel {
+    fn get_name(&self) -> &str {
+        match self {
+            LintLevel::Allow => "allow",
+            LintLevel::Warn => "warn",
+            LintLevel::Deny => "deny",
+        }
+    }
+}
+
+/// The level of every lint. All lints default to `LintLevel::Warn`
+pub struct LintConfig {
+    levels: Vec<(Lint, LintLevel)>,
+}
+
+impl LintConfig {
+    pub fn new() -> LintConfig {
+        LintConfig {
+            levels: Lint::iter().map(|l| (l, LintLevel::Warn)).collect(),
+        }
+    }
+
+    pub fn get_level(&self, lint: Lint) -> LintLevel {
+        self.levels
+            .iter()
+            .find(|(l, _)| *l == lint)
+            .map(|(_, level)| *level)
+            .unwrap()
+    }
+
+    pub fn set_level(&mut self, lint: Lint, level: LintLevel) {
+        for (l, l_level) in self.levels.iter_mut() {
+            if *l == lint {
+                *l_level = level;
+            }
+        }
+    }
+
+    pub fn set_all(&mut self, level: LintLevel) {
+        for (_, l_level) in self.levels.iter_mut() {
+            *l_level = level;
+        }
+    }
+
+    /// Sets the level of a lint by name, or every lint if `name` is `all`
+    pub fn set_level_by_name(&mut self, name: &str, level: LintLevel) -> Result<(), String> {
+        if name == "all" {
+            self.set_all(level);
+            return Ok(());
+        }
+        match Lint::from_name(name) {
+            Some(lint) => {
+                self.set_level(lint, level);
+                Ok(())
+            }
+            None => Err(format!("Lint '{}' not recognised", name)),
+        }
+    }
+}
+
+/// Returns the lint levels given with `ALLOW_FLAG`, `WARN_FLAG` and `DENY_FLAG` in `args`. Later
+/// flags override earlier ones
+pub fn get_lint_config(args: &[String]) -> Result<LintConfig, String> {
+    let mut config = LintConfig::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let Some((flag, level)) = [
+            (ALLOW_FLAG, LintLevel::Allow),
+            (WARN_FLAG, LintLevel::Warn),
+            (DENY_FLAG, LintLevel::Deny),
+        ]
+        .into_iter()
+        .find(|(flag, _)| arg.starts_with(flag)) else {
+            continue;
+        };
+
+        let name = match &arg[flag.len()..] {
+            "" => match args.next() {
+                Some(name) => name.as_str(),
+                None => {
+                    return Err(format!(
+                        "{} must be followed by the name of a lint or 'all'",
+                        flag
+                    ))
+                }
+            },
+            name => name,
+        };
+        config.set_level_by_name(name, level)?;
+    }
+
+    Ok(config)
+}
+
+/// What the linter needs to know about the names visible to a line. Implemented by the
+/// compiler's `ReferenceStack` so that names are resolved the same way as when compiling
+pub trait Scopes {
+    /// The number of nested scopes
+    fn get_depth(&self) -> usize;
+    /// Whether `name` refers to something visible from the innermost scope
+    fn is_visible(&self, name: &[String]) -> bool;
+    /// Whether `name` refers to a value known at compile time
+    fn is_constant(&self, name: &[String]) -> bool;
+}
+
+impl Scopes for ReferenceStack {
+    fn get_depth(&self) -> usize {
+        ReferenceStack::get_depth(self)
+    }
+
+    fn is_visible(&self, name: &[String]) -> bool {
+        self.get_reference(name).is_ok()
+    }
+
+    fn is_constant(&self, name: &[String]) -> bool {
+        self.get_reference(name)
+            .and_then(|reference| reference.get_variable_ref())
+            .is_ok_and(|variable| matches!(variable.get_address(), Address::Immediate(_)))
+    }
+}
+
+/// A variable declaration, referring to the name's position in `SymbolData::lines`
+struct Variable {
+    name: String,
+    line_index: usize,
+    path: Vec<usize>,
+    /// The depth of the scope the variable is in (see `Scopes::get_depth`)
+    depth: usize,
+    used: bool,
+}
+
+/// Checks each line for likely mistakes as it is compiled, reporting them to the
+/// `DiagnosticCollector` as warnings or as errors depending on the `LintConfig`
+pub struct Linter<'a> {
+    config: &'a LintConfig,
+    /// Variables at this depth aren't checked as they may be used by other files
+    top_level_depth: usize,
+    variables: Vec<Variable>,
+    /// The depths of the blocks that have reached their `else`
+    else_depths: Vec<usize>,
+}
+
+impl<'a> Linter<'a> {
+    pub fn new(config: &'a LintConfig, top_level_depth: usize) -> Linter<'a> {
+        Linter {
+            config,
+            top_level_depth,
+            variables: Vec::new(),
+            else_depths: Vec::new(),
+        }
+    }
+
+    fn report(&self, lint: Lint, diagnostic: Diagnostic, diagnostics: &mut DiagnosticCollector) {
+        let level = self.config.get_level(lint);
+        let mut diagnostic = diagnostic.with_code(lint.get_code()).with_note(format!(
+            "'{}' is set to {}",
+            lint.get_name(),
+            level.get_name()
+        ));
+        match level {
+            LintLevel::Allow => return,
+            LintLevel::Warn => {}
+            LintLevel::Deny => diagnostic.severity = Severity::Error,
+        }
+        diagnostics.push(diagnostic);
+    }
+
+    /// Stops tracking the variables in scopes deeper than `depth`, reporting those never used
+    fn close_scopes(
+        &mut self,
+        depth: usize,
+        symbol_data: &SymbolData,
+        diagnostics: &mut DiagnosticCollector,
+    ) {
+        let first_closed = self
+            .variables
+            .iter()
+            .position(|v| v.depth > depth)
+            .unwrap_or(self.variables.len());
+        for variable in self.variables.split_off(first_closed) {
+            if variable.used
+                || variable.depth <= self.top_level_depth
+                || variable.name.starts_with(UNUSED_NAME_PREFIX)
+            {
+                continue;
+            }
+            let diagnostic = Diagnostic::warning(format!("Unused variable '{}'", variable.name))
+                .with_primary(symbol_data.get_label(
+                    variable.line_index,
+                    &variable.path,
+                    String::new(),
+                ))
+                .with_help(format!(
+                    "If this is intentional, prefix it with an underscore: '{}{}'",
+                    UNUSED_NAME_PREFIX, variable.name
+                ));
+            self.report(Lint::UnusedVariable, diagnostic, diagnostics);
+        }
+    }
+
+    /// Marks the variable each name in `symbols` refers to as used
+    fn mark_used(&mut self, symbols: &[Symbol], scopes: &dyn Scopes) {
+        for symbol in symbols {
+            match symbol {
+                Symbol::Name(name) => {
+                    if !scopes.is_visible(&name[..1]) {
+                        continue;
+                    }
+                    if let Some(variable) =
+                        self.variables.iter_mut().rev().find(|v| v.name == name[0])
+                    {
+                        variable.used = true;
+                    }
+                }
+                Symbol::BracketedSection(symbols) => self.mark_used(symbols, scopes),
+                Symbol::Indexer(indexed, index) => {
+                    self.mark_used(std::slice::from_ref(indexed.as_ref()), scopes);
+                    self.mark_used(index, scopes);
+                }
+                Symbol::List(items) => {
+                    for item in items {
+                        self.mark_used(item, scopes);
+                    }
+                }
+                Symbol::MethodCall(value, _, arguments) => {
+                    self.mark_used(std::slice::from_ref(value.as_ref()), scopes);
+                    for argument in arguments {
+                        self.mark_used(argument, scopes);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Reports a declaration in an inner scope using the name of a visible variable
+    fn check_shadowing(
+        &self,
+        name: &[String],
+        line_index: usize,
+        path: &[usize],
+        scopes: &dyn Scopes,
+        symbol_data: &SymbolData,
+        diagnostics: &mut DiagnosticCollector,
+    ) {
+        if name.len() != 1 || !scopes.is_visible(name) {
+            return;
+        }
+        let Some(previous) = self
+            .variables
+            .iter()
+            .rev()
+            .find(|v| v.name == name[0])
+            .filter(|v| v.depth < scopes.get_depth())
+        else {
+            return;
+        };
+
+        let diagnostic = Diagnostic::warning(format!(
+            "'{}' shadows a variable in an outer scope",
+            name[0]
+        ))
+        .with_primary(symbol_data.get_label(line_index, path, String::new()))
+        .with_secondary(symbol_data.get_label(
+            previous.line_index,
+            &previous.path,
+            "previously declared here".to_string(),
+        ));
+        self.report(Lint::ShadowedName, diagnostic, diagnostics);
+    }
+
+    /// Returns true if `symbols` only uses literals and constants, so always evaluates to the same
+    /// value
+    fn is_constant(symbols: &[Symbol], scopes: &dyn Scopes) -> bool {
+        symbols.iter().all(|s| match s {
+            Symbol::Literal(_) | Symbol::Operator(_) => true,
+            Symbol::Name(name) => scopes.is_constant(name),
+            Symbol::BracketedSection(symbols) => Linter::is_constant(symbols, scopes),
+            _ => false,
+        })
+    }
+
+    fn check_condition(
+        &self,
+        line_index: usize,
+        symbols: &[Symbol],
+        scopes: &dyn Scopes,
+        symbol_data: &SymbolData,
+        diagnostics: &mut DiagnosticCollector,
+    ) {
+        let Some(Symbol::Block(block)) = symbols.first() else {
+            return;
+        };
+        let condition = &symbols[1..];
+        if condition.is_empty() || !Linter::is_constant(condition, scopes) {
+            return;
+        }
+
+        let mut diagnostic = Diagnostic::warning(format!(
+            "'{}' condition is always the same",
+            block.get_code_representation()
+        ))
+        .with_primary(symbol_data.get_range_label(
+            line_index,
+            1..symbols.len(),
+            String::new(),
+        ));
+        if *block == Block::While && condition == [Symbol::Literal(Literal::Bool(true))] {
+            diagnostic = diagnostic.with_help(format!(
+                "Use '{}' for an infinite loop",
+                Block::Loop.get_code_representation()
+            ));
+        }
+        self.report(Lint::ConstantCondition, diagnostic, diagnostics);
+    }
+
+    /// Checks a line before it is compiled, once the blocks it is outside of have been exited.
+    /// `block_type` is the type of the block the line is in
+    pub fn before_line(
+        &mut self,
+        line_index: usize,
+        block_type: &BlockType,
+        scopes: &dyn Scopes,
+        symbol_data: &SymbolData,
+        diagnostics: &mut DiagnosticCollector,
+    ) {
+        let depth = scopes.get_depth();
+        self.close_scopes(depth, symbol_data, diagnostics);
+        self.else_depths.retain(|d| *d <= depth);
+
+        let symbols = symbol_data.lines[line_index].symbols.as_slice();
+        match symbols.first() {
+            Some(Symbol::Block(Block::If | Block::While)) => {
+                self.check_condition(line_index, symbols, scopes, symbol_data, diagnostics);
+                self.mark_used(&symbols[1..], scopes);
+            }
+            //? Declarations only name their parameters
+            Some(Symbol::Block(Block::Function | Block::Class | Block::Enum)) => {}
+            Some(Symbol::Block(_)) => self.mark_used(&symbols[1..], scopes),
+            _ => {
+                let (declarations, value) = get_declarations(symbols);
+                //? The value is evaluated before the names are declared
+                self.mark_used(value, scopes);
+                //? Class and enum bodies declare members rather than variables
+                if matches!(block_type, BlockType::Class | BlockType::Enum) {
+                    return;
+                }
+                for (name, path) in declarations {
+                    self.check_shadowing(name, line_index, &path, scopes, symbol_data, diagnostics);
+                }
+            }
+        }
+    }
+
+    /// Records the variables declared by a line once it has compiled. `block_type` is the type of
+    /// the block the line is in
+    pub fn after_line(
+        &mut self,
+        line_index: usize,
+        block_type: &BlockType,
+        scopes: &dyn Scopes,
+        symbol_data: &SymbolData,
+    ) {
+        let symbols = symbol_data.lines[line_index].symbols.as_slice();
+        let declarations = match symbols.first() {
+            //? Parameters are in the scope of the function's body
+            Some(Symbol::Block(Block::Function)) => get_parameters(symbols),
+            Some(Symbol::Block(_)) => return,
+            _ if matches!(block_type, BlockType::Class | BlockType::Enum) => return,
+            _ => get_declarations(symbols).0,
+        };
+
+        for (name, path) in declarations {
+            let [name] = name.as_slice() else {
+                continue;
+            };
+            self.variables.push(Variable {
+                name: name.clone(),
+                line_index,
+                path,
+                depth: scopes.get_depth(),
+                used: false,
+            });
+        }
+    }
+
+    /// Checks a line such as `elif` or `else` that continues the block it follows, giving it a new
+    /// body in place of the previous one
+    pub fn continue_block(
+        &mut self,
+        line_index: usize,
+        scopes: &dyn Scopes,
+        symbol_data: &SymbolData,
+        diagnostics: &mut DiagnosticCollector,
+    ) {
+        let depth = scopes.get_depth();
+        self.close_scopes(depth.saturating_sub(1), symbol_data, diagnostics);
+
+        let symbols = symbol_data.lines[line_index].symbols.as_slice();
+        if let Some(Symbol::Block(block @ (Block::Elif | Block::Else))) = symbols.first() {
+            if self.else_depths.contains(&depth) {
+                let diagnostic = Diagnostic::warning(format!(
+                    "'{}' after '{}' will never be reached",
+                    block.get_code_representation(),
+                    Block::Else.get_code_representation()
+                ))
+                .with_primary(symbol_data.get_label(
+                    line_index,
+                    &[0],
+                    String::new(),
+                ));
+                self.report(Lint::ElifAfterElse, diagnostic, diagnostics);
+            } else if *block == Block::Else {
+                self.else_depths.push(depth);
+            }
+
+            if *block == Block::Elif {
+                self.check_condition(line_index, symbols, scopes, symbol_data, diagnostics);
+            }
+        }
+        self.mark_used(&symbols[1..], scopes);
+    }
+
+    /// Reports a line that will never be run. `reason` explains why
+    pub fn report_unreachable(
+        &self,
+        line_index: usize,
+        reason: String,
+        symbol_data: &SymbolData,
+        diagnostics: &mut DiagnosticCollector,
+    ) {
+        let symbols = &symbol_data.lines[line_index].symbols;
+        let diagnostic = Diagnostic::warning("Unreachable code".to_string())
+            .with_primary(symbol_data.get_range_label(line_index, 0..symbols.len(), String::new()))
+            .with_note(reason);
+        self.report(Lint::UnreachableCode, diagnostic, diagnostics);
+    }
+
+    /// Reports the variables still in scope once every line has been compiled
+    pub fn finish(mut self, symbol_data: &SymbolData, diagnostics: &mut DiagnosticCollector) {
+        self.close_scopes(self.top_level_depth, symbol_data, diagnostics);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::DEFAULT_ERROR_LIMIT;
+    use crate::lexical_parsing::preprocessor::convert_to_symbols;
+    use crate::lexical_parsing::symbols::Keyword;
+    use crate::processing::blocks::function_block::FunctionBlock;
+    use crate::processing::blocks::while_block::WhileBlock;
+    use crate::processing::blocks::BlockHandler;
+
+    /// An open block in `TestScopes`
+    struct TestScope {
+        block_type: BlockType,
+        /// The loop or function handler, which reports lines following a break or return
+        handler: Option<Box<dyn BlockHandler>>,
+        /// Names declared in the block and whether they are constant
+        names: Vec<(String, bool)>,
+    }
+
+    /// Stands in for the compiler's `ReferenceStack` and `BlockCoordinator`, with one scope per
+    /// level of indentation
+    struct TestScopes {
+        scopes: Vec<TestScope>,
+    }
+
+    impl TestScopes {
+        fn open(&mut self, block_type: BlockType, handler: Option<Box<dyn BlockHandler>>) {
+            self.scopes.push(TestScope {
+                block_type,
+                handler,
+                names: Vec::new(),
+            });
+        }
+
+        /// Returns the scopes a name can be found in - functions can't see outside themselves
+        fn visible(&self) -> &[TestScope] {
+            let start = self
+                .scopes
+                .iter()
+                .rposition(|s| matches!(s.block_type, BlockType::Function))
+                .unwrap_or(0);
+            &self.scopes[start..]
+        }
+
+        fn find(&self, name: &[String]) -> Option<bool> {
+            self.visible()
+                .iter()
+                .rev()
+                .find_map(|s| s.names.iter().rev().find(|(n, _)| *n == name[0]))
+                .map(|(_, constant)| *constant)
+        }
+    }
+
+    impl Scopes for TestScopes {
+        fn get_depth(&self) -> usize {
+            self.scopes.len()
+        }
+
+        fn is_visible(&self, name: &[String]) -> bool {
+            self.find(name).is_some()
+        }
+
+        fn is_constant(&self, name: &[String]) -> bool {
+            self.find(name) == Some(true)
+        }
+    }
+
+    /// Lints `source` the way `process_symbols` does, returning the code and message of each
+    /// diagnostic
+    fn lint(
+        name: &str,
+        source: &str,
+        config: &LintConfig,
+    ) -> Vec<(Severity, &'static str, String)> {
+        let path = std::env::temp_dir().join(format!("whython_lint_{}.why", name));
+        std::fs::write(&path, source).unwrap();
+
+        let mut symbol_data = SymbolData::new();
+        let mut diagnostics = DiagnosticCollector::new(DEFAULT_ERROR_LIMIT);
+        convert_to_symbols(
+            path.to_string_lossy().to_string(),
+            &mut symbol_data,
+            &mut diagnostics,
+        )
+        .unwrap();
+        assert!(
+            !diagnostics.has_errors(),
+            "{:?}",
+            diagnostics.get_diagnostics()
+        );
+
+        let mut scopes = TestScopes { scopes: Vec::new() };
+        scopes.open(BlockType::Base, None);
+        let mut linter = Linter::new(config, scopes.get_depth());
+
+        'lines: for (line_index, line) in symbol_data.lines.iter().enumerate() {
+            let symbols = line.symbols.as_slice();
+            while line.indentation + 1 < scopes.get_depth() {
+                let continues = line.indentation + 2 == scopes.get_depth()
+                    && matches!(
+                        symbols.first(),
+                        Some(Symbol::Block(Block::Elif | Block::Else))
+                    );
+                if continues {
+                    scopes.scopes.last_mut().unwrap().names.clear();
+                    linter.continue_block(line_index, &scopes, &symbol_data, &mut diagnostics);
+                    continue 'lines;
+                }
+                scopes.scopes.pop();
+            }
+
+            let block_type = scopes.scopes.last().unwrap().block_type;
+            linter.before_line(
+                line_index,
+                &block_type,
+                &scopes,
+                &symbol_data,
+                &mut diagnostics,
+            );
+            if let Some(handler) = scopes.scopes.last_mut().unwrap().handler.as_mut() {
+                if let Err(reason) = handler.handle_line(symbols) {
+                    linter.report_unreachable(line_index, reason, &symbol_data, &mut diagnostics);
+                }
+            }
+
+            match symbols.first() {
+                Some(Symbol::Block(Block::Function)) => {
+                    scopes.open(BlockType::Function, Some(FunctionBlock::new_block()))
+                }
+                Some(Symbol::Block(Block::While | Block::Loop)) => {
+                    scopes.open(BlockType::While, Some(WhileBlock::new_block()))
+                }
+                Some(Symbol::Block(Block::Class)) => scopes.open(BlockType::Class, None),
+                Some(Symbol::Block(Block::Enum)) => scopes.open(BlockType::Enum, None),
+                Some(Symbol::Block(Block::If)) => scopes.open(BlockType::If, None),
+                Some(Symbol::Block(_)) => scopes.open(BlockType::Base, None),
+                _ => {
+                    let constant = matches!(symbols.first(), Some(Symbol::Keyword(Keyword::Const)));
+                    for (name, _) in get_declarations(symbols).0 {
+                        let scope = scopes.scopes.last_mut().unwrap();
+                        scope.names.push((name[0].clone(), constant));
+                    }
+                }
+            }
+            if let Some(Symbol::Block(Block::Function)) = symbols.first() {
+                for (name, _) in get_parameters(symbols) {
+                    let scope = scopes.scopes.last_mut().unwrap();
+                    scope.names.push((name[0].clone(), false));
+                }
+            }
+            linter.after_line(line_index, &block_type, &scopes, &symbol_data);
+        }
+        linter.finish(&symbol_data, &mut diagnostics);
+
+        diagnostics
+            .get_diagnostics()
+            .iter()
+            .map(|d| (d.severity, d.code.unwrap(), d.message.clone()))
+            .collect()
+    }
+
+    fn warning(code: &'static str, message: &str) -> (Severity, &'static str, String) {
+        (Severity::Warning, code, message.to_string())
+    }
+
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn unused_variables_and_parameters() {
+        let source = "fn f (int a, int _b)\n    int c = 1\n    int d = c\n    print d\n";
+        assert_eq!(
+            lint("unused", source, &LintConfig::new()),
+            vec![warning(UNUSED_VARIABLE_WARNING, "Unused variable 'a'")]
+        );
+
+        //? Functions can't use variables outside of them so the outer 'i' is never used
+        let source = "if true\n    int i = 0\n    fn f (int i)\n        print i\n";
+        assert_eq!(
+            lint("unused_outer", source, &LintConfig::new())
+                .into_iter()
+                .filter(|(_, code, _)| *code == UNUSED_VARIABLE_WARNING)
+                .collect::<Vec<_>>(),
+            vec![warning(UNUSED_VARIABLE_WARNING, "Unused variable 'i'")]
+        );
+    }
+
+    #[test]
+    fn unreachable_code() {
+        let source = "int i = 0\nloop\n    break\n    i = 1\n    i = 2\nprint i\n";
+        assert_eq!(
+            lint("unreachable", source, &LintConfig::new()),
+            vec![warning(UNREACHABLE_CODE_WARNING, "Unreachable code")]
+        );
+
+        let source = "fn f () int\n    return 1\n    print 2\nint a = f()\nprint a\n";
+        assert_eq!(
+            lint("unreachable_return", source, &LintConfig::new()),
+            vec![warning(UNREACHABLE_CODE_WARNING, "Unreachable code")]
+        );
+
+        //? A break in a nested block may not run
+        let source = "int i = 0\nloop\n    if i == 1\n        break\n    i = 1\n";
+        assert_eq!(lint("reachable", source, &LintConfig::new()), vec![]);
+    }
+
+    #[test]
+    fn elif_after_else() {
+        let source =
+            "int i = 0\nif i == 1\n    print i\nelse\n    print i\nelif i == 2\n    print i\n";
+        assert_eq!(
+            lint("elif", source, &LintConfig::new()),
+            vec![warning(
+                ELIF_AFTER_ELSE_WARNING,
+                "'elif' after 'else' will never be reached"
+            )]
+        );
+    }
+
+    #[test]
+    fn shadowed_names() {
+        let source = "int i = 0\nif i == 1\n    int i = 2\n    print i\n";
+        assert_eq!(
+            lint("shadowed", source, &LintConfig::new()),
+            vec![warning(
+                SHADOWED_NAME_WARNING,
+                "'i' shadows a variable in an outer scope"
+            )]
+        );
+
+        //? Parameters can't see outside the function so don't shadow
+        let source = "int i = 0\nfn f (int j)\n    int i = j\n    print i\nprint i\n";
+        assert_eq!(lint("not_shadowed", source, &LintConfig::new()), vec![]);
+    }
+
+    #[test]
+    fn constant_conditions() {
+        let source =
+            "const int n = 1\nint i = 0\nwhile true\n    break\nif n == 2\n    print 1\nif i == 2\n    print 1\n";
+        assert_eq!(
+            lint("constant", source, &LintConfig::new()),
+            vec![
+                warning(
+                    CONSTANT_CONDITION_WARNING,
+                    "'while' condition is always the same"
+                ),
+                warning(
+                    CONSTANT_CONDITION_WARNING,
+                    "'if' condition is always the same"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn lint_levels() {
+        let source = "int i = 0\nif i == 1\n    int i = 2\n    print i\nwhile true\n    break\n";
+
+        let mut config = LintConfig::new();
+        config
+            .set_level_by_name("shadowed_name", LintLevel::Deny)
+            .unwrap();
+        config
+            .set_level_by_name("constant_condition", LintLevel::Allow)
+            .unwrap();
+        assert_eq!(
+            lint("levels", source, &config),
+            vec![(
+                Severity::Error,
+                SHADOWED_NAME_WARNING,
+                "'i' shadows a variable in an outer scope".to_string()
+            )]
+        );
+
+        config.set_level_by_name("all", LintLevel::Allow).unwrap();
+        assert_eq!(lint("levels_all", source, &config), vec![]);
+        assert!(config
+            .set_level_by_name("not_a_lint", LintLevel::Warn)
+            .is_err());
+    }
+
+    #[test]
+    fn lint_levels_from_args() {
+        let config = get_lint_config(&to_args(&[
+            "main.why",
+            "-D",
+            "all",
+            "-Aunused_variable",
+            "--format",
+            "json",
+            "-W",
+            "shadowed_name",
+        ]))
+        .unwrap();
+        assert_eq!(config.get_level(Lint::UnusedVariable), LintLevel::Allow);
+        assert_eq!(config.get_level(Lint::ShadowedName), LintLevel::Warn);
+        assert_eq!(config.get_level(Lint::UnreachableCode), LintLevel::Deny);
+
+        assert!(get_lint_config(&to_args(&["main.why", "-D"])).is_err());
+        assert!(get_lint_config(&to_args(&["-W", "not_a_lint"])).is_err());
+    }
+}
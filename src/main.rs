@@ -1,7 +1,23 @@
-use crate::memory::runtime_memory::heap_memory::HeapMemory;
+use std::env;
+use std::path::Path;
+use std::process::ExitCode;
+
+use crate::compilation::processor::process_symbols;
+use crate::diagnostics::{
+    get_error_limit, get_output_format, DiagnosticCollector, OutputFormat, ERROR_LIMIT_FLAG,
+    OUTPUT_FORMAT_FLAG,
+};
+use crate::error::BoxedError;
+use crate::file_util::{get_search_paths, SEARCH_PATH_FLAG};
+use crate::lexical_parsing::preprocessor::{
+    convert_to_symbols, SymbolData, DOC_COMMENTS_FLAG, IMPORT_GRAPH_FLAG,
+};
+use crate::lints::{get_lint_config, ALLOW_FLAG, DENY_FLAG, WARN_FLAG};
 
 mod memory;
 mod error;
+mod diagnostics;
+mod lints;
 mod execution;
 mod compilation;
 mod util;
@@ -9,16 +25,167 @@ mod instructions;
 mod lexical_parsing;
 mod file_util;
 
-fn main() {
-    let mut heap = HeapMemory::new(32);
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match compile(&args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Compiles the file given in `args`, writing any diagnostics to stdout in the chosen format.
+/// Returns whether the file compiled without errors
+fn compile(args: &[String]) -> Result<bool, BoxedError> {
+    let file_name = get_file_name(args)?;
+    let format = get_output_format(args)?;
+    let lint_config = get_lint_config(args)?;
+
+    let mut diagnostics = DiagnosticCollector::new(get_error_limit(args)?);
+    let mut symbol_data = SymbolData::new();
+    symbol_data.set_search_paths(get_search_paths(args)?);
+    convert_to_symbols(file_name.clone(), &mut symbol_data, &mut diagnostics)?;
+
+    //? Lists each loaded file and its imports for tooling instead of compiling
+    if args.iter().any(|a| a == IMPORT_GRAPH_FLAG) {
+        print_diagnostics(&diagnostics, format, &symbol_data);
+        for (file_name, imports) in symbol_data.get_import_graph() {
+            println!("{}: {}", file_name, imports.join(", "));
+        }
+        return Ok(!diagnostics.has_errors());
+    }
+
+    //? Lists each documented declaration for documentation tools instead of compiling
+    if args.iter().any(|a| a == DOC_COMMENTS_FLAG) {
+        print_diagnostics(&diagnostics, format, &symbol_data);
+        for line_index in 0..symbol_data.lines.len() {
+            if let Some(doc_comment) = symbol_data.get_doc_comment(line_index) {
+                println!(
+                    "{}\n{}\n",
+                    symbol_data.get_error_path(line_index),
+                    doc_comment
+                );
+            }
+        }
+        return Ok(!diagnostics.has_errors());
+    }
+
+    //? Compiling after lexer errors would mostly report errors caused by the skipped lines
+    let program_memory = if diagnostics.has_errors() {
+        None
+    } else {
+        Some(process_symbols(
+            &mut symbol_data,
+            &lint_config,
+            &mut diagnostics,
+        ))
+    };
+
+    print_diagnostics(&diagnostics, format, &symbol_data);
 
-    heap.allocate(2);
+    match program_memory {
+        Some(program_memory) if !diagnostics.has_errors() => {
+            let output_name = Path::new(file_name).with_extension("");
+            program_memory.save_to_file(output_name.to_string_lossy().to_string());
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Writes the diagnostics to stdout in `format`
+fn print_diagnostics(
+    diagnostics: &DiagnosticCollector,
+    format: OutputFormat,
+    sources: &SymbolData,
+) {
+    let output = diagnostics.render_as(format, sources);
+    match format {
+        OutputFormat::Human if !output.is_empty() => println!("{}", output),
+        //? Each line is already terminated
+        OutputFormat::JsonLines => print!("{}", output),
+        _ => {}
+    }
+}
+
+/// Returns the only argument that isn't a flag or a flag's value. Flags may come in any order
+fn get_file_name(args: &[String]) -> Result<&String, String> {
+    const VALUE_FLAGS: [&str; 6] = [
+        ERROR_LIMIT_FLAG,
+        OUTPUT_FORMAT_FLAG,
+        SEARCH_PATH_FLAG,
+        ALLOW_FLAG,
+        WARN_FLAG,
+        DENY_FLAG,
+    ];
+    //? These can also be joined to their value e.g. -Ilib
+    const ATTACHED_FLAGS: [&str; 4] = [SEARCH_PATH_FLAG, ALLOW_FLAG, WARN_FLAG, DENY_FLAG];
+
+    let mut file_names = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if VALUE_FLAGS.contains(&arg.as_str()) {
+            args.next();
+        } else if !arg.starts_with('-') {
+            file_names.push(arg);
+        } else if ![IMPORT_GRAPH_FLAG, DOC_COMMENTS_FLAG].contains(&arg.as_str())
+            && !ATTACHED_FLAGS.iter().any(|flag| arg.starts_with(flag))
+        {
+            return Err(format!("Unrecognised option '{}'", arg));
+        }
+    }
+
+    match file_names.as_slice() {
+        [file_name] => Ok(file_name),
+        _ => Err(format!(
+            "Usage: whython [file] [{} number] [{} human|json] [{} directory]... [{}|{}|{} lint]... [{}|{}]",
+            ERROR_LIMIT_FLAG,
+            OUTPUT_FORMAT_FLAG,
+            SEARCH_PATH_FLAG,
+            ALLOW_FLAG,
+            WARN_FLAG,
+            DENY_FLAG,
+            IMPORT_GRAPH_FLAG,
+            DOC_COMMENTS_FLAG
+        )),
+    }
+}
 
-    heap.allocate(10);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    heap.free(1);
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
 
-    heap.allocate(2);
+    #[test]
+    fn finds_file_among_flags() {
+        for args in [
+            to_args(&["main.why"]),
+            to_args(&["-I", "lib", "main.why"]),
+            to_args(&["-Ilib", "main.why", "--format", "json"]),
+            to_args(&["--error-limit", "3", "main.why"]),
+            to_args(&["main.why", "--format", "json"]),
+            to_args(&["--imports", "main.why"]),
+            to_args(&["main.why", "--docs"]),
+            to_args(&["--format", "json", "--error-limit", "3", "main.why"]),
+            to_args(&["-A", "unused_variable", "main.why", "-Dall"]),
+        ] {
+            assert_eq!(get_file_name(&args).unwrap(), "main.why");
+        }
+    }
 
-    heap.dump_usage();
+    #[test]
+    fn rejects_missing_or_extra_files() {
+        assert!(get_file_name(&to_args(&[])).is_err());
+        assert!(get_file_name(&to_args(&["--error-limit", "main.why"])).is_err());
+        assert!(get_file_name(&to_args(&["-I", "main.why"])).is_err());
+        assert!(get_file_name(&to_args(&["-W", "main.why"])).is_err());
+        assert!(get_file_name(&to_args(&["a.why", "b.why"])).is_err());
+        assert!(get_file_name(&to_args(&["main.why", "--verbose"])).is_err());
+    }
 }
@@ -3,7 +3,9 @@ use crate::memory::address::Address::{HeapDirect, HeapIndirect, Immediate, Progr
 use crate::memory::runtime_memory::RuntimeMemory;
 use crate::util::{read_usize, USIZE_BYTES};
 
-const ADDRESS_CODE_BYTES: usize = 1;
+pub const ADDRESS_CODE_BYTES: usize = 1;
+/// Size of a serialised non-immediate address (e.g. the value of a typed pointer)
+pub const DIRECT_ADDRESS_BYTES: usize = ADDRESS_CODE_BYTES + USIZE_BYTES;
 const IMMEDIATE_CODE: u8 = 0;
 const STACK_DIRECT_CODE: u8 = 2;
 const STACK_INDIRECT_CODE: u8 = 3;
@@ -85,6 +87,29 @@ impl Address {
     }
 
 
+    /// Returns the address `offset` bytes after this one. Returns `None` for indirect addresses
+    /// as the offset can't be applied until runtime
+    pub fn offset(&self, offset: usize) -> Option<Address> {
+        match self {
+            Immediate(data) => data.get(offset..).map(|data| Immediate(Vec::from(data))),
+            StackDirect(address) => Some(StackDirect(address + offset)),
+            HeapDirect(address) => Some(HeapDirect(address + offset)),
+            ProgramDirect(address) => Some(ProgramDirect(address + offset)),
+            StackIndirect(_) | HeapIndirect(_) | ProgramIndirect(_) => None
+        }
+    }
+
+    /// Returns an address that follows the address stored at this one i.e. the target of a pointer
+    /// stored here. Returns `None` if this address isn't direct
+    pub fn to_indirect(&self) -> Option<Address> {
+        match self {
+            StackDirect(address) => Some(StackIndirect(*address)),
+            HeapDirect(address) => Some(HeapIndirect(*address)),
+            ProgramDirect(address) => Some(ProgramIndirect(*address)),
+            _ => None
+        }
+    }
+
     pub fn get_bytes(&self) -> Vec<u8> {
         fn make_vec(code: u8, address: usize) -> Vec<u8> {
             let mut v = Vec::with_capacity(ADDRESS_CODE_BYTES + USIZE_BYTES);
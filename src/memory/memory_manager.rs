@@ -7,12 +7,25 @@ use crate::memory::runtime_memory::program_cursor::ProgramCursor;
 #[derive(Default)]
 pub struct MemoryManager {
     pub memory: Vec<u8>,
+    /// Where the code being compiled comes from e.g. `main.why - Line 3`. Embedded in
+    /// instructions that can fail at runtime
+    source_location: String,
 }
 
 impl MemoryManager {
     /// Creates an empty memory manager
     pub fn new() -> Self {
-        Self { memory: Vec::new() }
+        Self { memory: Vec::new(), source_location: String::new() }
+    }
+
+    /// Sets the location of the code currently being compiled
+    pub fn set_source_location(&mut self, source_location: String) {
+        self.source_location = source_location;
+    }
+
+    /// Gets the location of the code currently being compiled
+    pub fn get_source_location(&self) -> &str {
+        &self.source_location
     }
 
     /// Gets the position after the last piece of memory written
@@ -76,12 +89,13 @@ impl MemoryManager {
         }
     }
 
-    /// Saves compiled data to a file with the specified name (excluding extension)
+    /// Saves compiled data to a file with the specified name (excluding extension). Progress is
+    /// written to stderr so that stdout only contains diagnostics
     //noinspection SpellCheckingInspection
     pub fn save_to_file(&self, name: String) {
         let name = name + format!(" - {}.cwhy", usize::BITS).as_str();
 
-        println!(
+        eprintln!(
             "Saving data '{}' [{} bytes]",
             &name,
             self.memory.len().to_formatted_string(&Locale::en)
@@ -94,12 +108,12 @@ impl MemoryManager {
             .open(name);
 
         let Ok(mut file) = file else {
-            println!("Failed to open file - {}", file.unwrap_err());
+            eprintln!("Failed to open file - {}", file.unwrap_err());
             return;
         };
 
         if let Err(e) = file.write_all(&self.memory) {
-            println!("Failed to write to file - {}", e)
+            eprintln!("Failed to write to file - {}", e)
         }
     }
 
@@ -109,13 +123,13 @@ impl MemoryManager {
 
         let data = fs::read(path)?;
 
-        Ok(Self { memory: data })
+        Ok(Self { memory: data, source_location: String::new() })
     }
 }
 
 impl From<Vec<u8>> for MemoryManager {
     fn from(value: Vec<u8>) -> Self {
-        MemoryManager { memory: value }
+        MemoryManager { memory: value, source_location: String::new() }
     }
 }
 
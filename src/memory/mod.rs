@@ -1,5 +1,5 @@
 mod memory_manager;
-mod address;
+pub mod address;
 pub mod runtime_memory;
 
 pub use memory_manager::MemoryManager;
\ No newline at end of file
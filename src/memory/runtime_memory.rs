@@ -2,6 +2,8 @@ mod stack_memory;
 pub mod heap_memory;
 pub mod program_cursor;
 
+use either::{Left, Right};
+use crate::memory::address::Address;
 use crate::memory::runtime_memory::heap_memory::HeapMemory;
 use crate::memory::runtime_memory::program_cursor::ProgramCursor;
 use crate::memory::runtime_memory::stack_memory::StackMemory;
@@ -19,4 +21,23 @@ impl RuntimeMemory {
     pub fn stack_mut(&mut self) -> &mut StackMemory { &mut self.stack }
     pub fn heap(&self) -> &HeapMemory { &self.heap }
     pub fn heap_mut(&mut self) -> &mut HeapMemory { &mut self.heap }
+
+    /// Reads `length` bytes from the memory `address` refers to
+    pub fn read(&self, address: Address, length: usize) -> Vec<u8> {
+        match address.evaluate_address_to_data(self) {
+            Left(data) => Vec::from(&data[..length]),
+            Right(data) => data,
+        }
+    }
+
+    /// Writes `data` to the memory `address` refers to
+    pub fn write(&mut self, address: Address, data: &[u8]) {
+        let location = match address.evaluate_to_direct(self) {
+            Address::StackDirect(address) => self.stack.get_location_mut(address),
+            Address::HeapDirect(address) => self.heap.get_location_mut(address),
+            _ => panic!("Only stack and heap memory can be written to at runtime"),
+        };
+
+        location[..data.len()].copy_from_slice(data);
+    }
 }
\ No newline at end of file
@@ -2,6 +2,7 @@ pub(crate) mod ref_or_box;
 
 use std::{fs, mem};
 use std::io::Write;
+use std::path::PathBuf;
 
 pub const USIZE_BYTES: usize = mem::size_of::<usize>();
 
@@ -9,6 +10,25 @@ pub fn read_usize(data: &[u8]) -> usize {
     usize::from_le_bytes((data[..USIZE_BYTES]).try_into().unwrap())
 }
 
+/// Reads a little-endian integer of `data.len()` (at most 16) bytes, sign-extending it if `signed`
+pub fn read_int(data: &[u8], signed: bool) -> i128 {
+    let mut bytes = [0u8; 16];
+    bytes[..data.len()].copy_from_slice(data);
+
+    if signed && data.last().is_some_and(|b| b & 0x80 != 0) {
+        for b in bytes[data.len()..].iter_mut() {
+            *b = 0xFF;
+        }
+    }
+
+    i128::from_le_bytes(bytes)
+}
+
+/// Returns the lowest `length` bytes of `value` in little-endian order
+pub fn int_to_bytes(value: i128, length: usize) -> Vec<u8> {
+    Vec::from(&value.to_le_bytes()[..length])
+}
+
 pub fn dump_bytes(file: &str, data: &[u8]) {
     let mut file = fs::OpenOptions::new()
         .write(true)
@@ -20,17 +40,9 @@ pub fn dump_bytes(file: &str, data: &[u8]) {
     file.write_all(data).unwrap();
 }
 
-// TODO: Replace all instances of this with proper path handling
-pub fn join_file_name(name: &[String]) -> String {
-    let mut joined_name = String::new();
-    for (i, string) in name.iter().enumerate() {
-        joined_name += string;
-
-        if i < name.len() - 2 {
-            joined_name.push('/');
-        } else if i == name.len() - 2 {
-            joined_name.push('.')
-        }
-    }
-    joined_name
+/// Converts the name of an imported file to a relative path e.g. `lib.maths.why` -> `lib/maths.why`
+pub fn get_import_path(name: &[String]) -> PathBuf {
+    let mut path: PathBuf = name[..name.len() - 2].iter().collect();
+    path.push(format!("{}.{}", name[name.len() - 2], name[name.len() - 1]));
+    path
 }